@@ -0,0 +1,299 @@
+/*!
+Pluggable evaluation backends.
+
+The circuit scheduling code is independent of how individual gates are
+computed. A [`Backend`] supplies the ciphertext and key types plus one method
+per gate, and [`Circuit::evaluate_with`] runs any circuit on any backend.
+
+The crate ships three backends:
+
+- [`TfheBackend`]: homomorphic evaluation with TFHE Boolean server keys (used
+  by [`EncryptedCircuit`])
+- [`PlaintextBackend`]: evaluation on plain `bool`s, useful as a reference
+  simulation of a circuit
+- [`MockBackend`]: a fast, **insecure** stand-in for TFHE that wraps booleans
+  in opaque [`MockCiphertext`]s and counts the gates it evaluates, for tests
+
+Hardware accelerated TFHE (the `gpu` and `hpu` features) can be added as
+further implementations without touching the circuit code.
+
+## Example
+
+```rust
+use encircuit::prelude::*;
+use encircuit::{MockBackend, MockCiphertext, PlaintextBackend};
+# fn main() -> anyhow::Result<()> {
+let mut builder = CircuitBuilder::default();
+let x = builder.input();
+let y = builder.input();
+let output = builder.xor(x, y);
+let circuit = builder.finish(output);
+
+// Plaintext reference evaluation
+let result = circuit.evaluate_with(&PlaintextBackend, &(), &[true, false])?;
+assert!(result[0]);
+
+// Same circuit on the mock backend
+let backend = MockBackend::new();
+let inputs = [MockCiphertext::encrypt(true), MockCiphertext::encrypt(false)];
+let result = circuit.evaluate_with(&backend, &(), &inputs)?;
+assert!(result[0].decrypt());
+assert_eq!(backend.bootstrap_count(), 1);
+# Ok(())
+# }
+```
+
+[`Circuit::evaluate_with`]: crate::Circuit::evaluate_with
+[`EncryptedCircuit`]: crate::EncryptedCircuit
+*/
+
+use crate::ciphertext::BoolCt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tfhe::boolean::prelude::BinaryBooleanGates;
+
+/// A gate-level evaluation backend.
+///
+/// Implementations define the representation of encrypted (or plain) bits and
+/// the key needed to combine them. Every method corresponds to one [`Gate`]
+/// type; inputs are provided by the caller of the evaluator.
+///
+/// [`Gate`]: crate::circuit::Gate
+pub trait Backend {
+    /// The value type flowing along circuit wires.
+    type Ciphertext: Clone;
+
+    /// The key needed to evaluate gates (e.g. a TFHE server key).
+    type Key: ?Sized;
+
+    /// Materialize a constant as a ciphertext.
+    ///
+    /// The evaluator folds constants into the gates consuming them, so this is
+    /// only called when a constant value reaches a circuit output.
+    fn constant(&self, key: &Self::Key, value: bool) -> Self::Ciphertext;
+
+    /// Evaluate an AND gate.
+    fn and(&self, key: &Self::Key, left: &Self::Ciphertext, right: &Self::Ciphertext) -> Self::Ciphertext;
+
+    /// Evaluate an OR gate.
+    fn or(&self, key: &Self::Key, left: &Self::Ciphertext, right: &Self::Ciphertext) -> Self::Ciphertext;
+
+    /// Evaluate an XOR gate.
+    fn xor(&self, key: &Self::Key, left: &Self::Ciphertext, right: &Self::Ciphertext) -> Self::Ciphertext;
+
+    /// Evaluate a NOT gate.
+    fn not(&self, key: &Self::Key, input: &Self::Ciphertext) -> Self::Ciphertext;
+
+    /// Compute the majority of three values.
+    ///
+    /// Used to merge the replicas of redundantly evaluated sub-circuits. The
+    /// default implementation computes `(a AND b) OR (c AND (a OR b))` with
+    /// four binary gates; backends with a cheaper primitive should override it.
+    fn majority(
+        &self,
+        key: &Self::Key,
+        a: &Self::Ciphertext,
+        b: &Self::Ciphertext,
+        c: &Self::Ciphertext,
+    ) -> Self::Ciphertext {
+        let both = self.and(key, a, b);
+        let either = self.or(key, a, b);
+        let third = self.and(key, c, &either);
+        self.or(key, &both, &third)
+    }
+}
+
+/// Homomorphic evaluation with TFHE Boolean keys.
+///
+/// Constants that reach an output are materialized as trivial (noiseless,
+/// unencrypted) TFHE ciphertexts, so no client key is needed on the server.
+/// Gate results inherit the key id of their operands.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TfheBackend;
+
+impl Backend for TfheBackend {
+    type Ciphertext = BoolCt;
+    type Key = tfhe::boolean::prelude::ServerKey;
+
+    fn constant(&self, key: &Self::Key, value: bool) -> BoolCt {
+        BoolCt::from_tfhe_ciphertext(key.trivial_encrypt(value))
+    }
+
+    fn and(&self, key: &Self::Key, left: &BoolCt, right: &BoolCt) -> BoolCt {
+        BoolCt::from_tfhe_ciphertext(key.and(left.tfhe_ciphertext(), right.tfhe_ciphertext()))
+            .with_key_id(left.key_id().or(right.key_id()))
+    }
+
+    fn or(&self, key: &Self::Key, left: &BoolCt, right: &BoolCt) -> BoolCt {
+        BoolCt::from_tfhe_ciphertext(key.or(left.tfhe_ciphertext(), right.tfhe_ciphertext()))
+            .with_key_id(left.key_id().or(right.key_id()))
+    }
+
+    fn xor(&self, key: &Self::Key, left: &BoolCt, right: &BoolCt) -> BoolCt {
+        BoolCt::from_tfhe_ciphertext(key.xor(left.tfhe_ciphertext(), right.tfhe_ciphertext()))
+            .with_key_id(left.key_id().or(right.key_id()))
+    }
+
+    fn not(&self, key: &Self::Key, input: &BoolCt) -> BoolCt {
+        BoolCt::from_tfhe_ciphertext(key.not(input.tfhe_ciphertext())).with_key_id(input.key_id())
+    }
+
+    /// Computes `(a XOR b) ? c : a`, which costs three bootstraps.
+    fn majority(&self, key: &Self::Key, a: &BoolCt, b: &BoolCt, c: &BoolCt) -> BoolCt {
+        let differ = key.xor(a.tfhe_ciphertext(), b.tfhe_ciphertext());
+        BoolCt::from_tfhe_ciphertext(key.mux(&differ, c.tfhe_ciphertext(), a.tfhe_ciphertext()))
+            .with_key_id(a.key_id().or(b.key_id()).or(c.key_id()))
+    }
+}
+
+/// Evaluation on plain booleans.
+///
+/// Runs the exact same scheduling code as the homomorphic backends, which makes
+/// it the reference simulation for a circuit. Provides no confidentiality.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlaintextBackend;
+
+impl Backend for PlaintextBackend {
+    type Ciphertext = bool;
+    type Key = ();
+
+    fn constant(&self, _key: &(), value: bool) -> bool {
+        value
+    }
+
+    fn and(&self, _key: &(), left: &bool, right: &bool) -> bool {
+        *left && *right
+    }
+
+    fn or(&self, _key: &(), left: &bool, right: &bool) -> bool {
+        *left || *right
+    }
+
+    fn xor(&self, _key: &(), left: &bool, right: &bool) -> bool {
+        *left ^ *right
+    }
+
+    fn not(&self, _key: &(), input: &bool) -> bool {
+        !*input
+    }
+}
+
+/// An opaque bit produced by the [`MockBackend`].
+///
+/// **Not encrypted.** The value is only hidden behind explicit
+/// [`MockCiphertext::encrypt`] / [`MockCiphertext::decrypt`] calls so tests
+/// exercise the same data flow as real ciphertexts.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct MockCiphertext {
+    value: bool,
+}
+
+impl MockCiphertext {
+    /// Wrap a plaintext bit into a mock ciphertext.
+    pub fn encrypt(value: bool) -> Self {
+        Self { value }
+    }
+
+    /// Reveal the wrapped plaintext bit.
+    pub fn decrypt(&self) -> bool {
+        self.value
+    }
+}
+
+impl std::fmt::Debug for MockCiphertext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockCiphertext")
+            .field("encrypted", &"<mock_data>")
+            .finish()
+    }
+}
+
+/// A fast, insecure backend for tests.
+///
+/// Behaves like a homomorphic backend without any cryptography, and counts
+/// how many gates of each kind it evaluated. Binary gates are counted as
+/// bootstraps, matching the cost model of TFHE Boolean gates; a majority is
+/// counted as the three bootstraps it costs with [`TfheBackend`].
+#[derive(Debug, Default)]
+pub struct MockBackend {
+    constants: AtomicUsize,
+    bootstraps: AtomicUsize,
+    negations: AtomicUsize,
+    majorities: AtomicUsize,
+}
+
+impl MockBackend {
+    /// Create a mock backend with all counters at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of binary gates (AND, OR, XOR) evaluated so far.
+    pub fn bootstrap_count(&self) -> usize {
+        self.bootstraps.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of NOT gates evaluated so far.
+    pub fn not_count(&self) -> usize {
+        self.negations.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of majority gates evaluated so far.
+    pub fn majority_count(&self) -> usize {
+        self.majorities.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of constants materialized so far.
+    ///
+    /// Constants are folded during evaluation, so this only counts constants
+    /// that reached a circuit output.
+    pub fn constant_count(&self) -> usize {
+        self.constants.load(Ordering::Relaxed)
+    }
+
+    /// Reset all counters to zero.
+    pub fn reset_counts(&self) {
+        self.constants.store(0, Ordering::Relaxed);
+        self.bootstraps.store(0, Ordering::Relaxed);
+        self.negations.store(0, Ordering::Relaxed);
+        self.majorities.store(0, Ordering::Relaxed);
+    }
+
+    /// Count one binary gate and wrap its result.
+    fn binary(&self, value: bool) -> MockCiphertext {
+        self.bootstraps.fetch_add(1, Ordering::Relaxed);
+        MockCiphertext::encrypt(value)
+    }
+}
+
+impl Backend for MockBackend {
+    type Ciphertext = MockCiphertext;
+    type Key = ();
+
+    fn constant(&self, _key: &(), value: bool) -> MockCiphertext {
+        self.constants.fetch_add(1, Ordering::Relaxed);
+        MockCiphertext::encrypt(value)
+    }
+
+    fn and(&self, _key: &(), left: &MockCiphertext, right: &MockCiphertext) -> MockCiphertext {
+        self.binary(left.value && right.value)
+    }
+
+    fn or(&self, _key: &(), left: &MockCiphertext, right: &MockCiphertext) -> MockCiphertext {
+        self.binary(left.value || right.value)
+    }
+
+    fn xor(&self, _key: &(), left: &MockCiphertext, right: &MockCiphertext) -> MockCiphertext {
+        self.binary(left.value ^ right.value)
+    }
+
+    fn not(&self, _key: &(), input: &MockCiphertext) -> MockCiphertext {
+        self.negations.fetch_add(1, Ordering::Relaxed);
+        MockCiphertext::encrypt(!input.value)
+    }
+
+    fn majority(&self, _key: &(), a: &MockCiphertext, b: &MockCiphertext, c: &MockCiphertext) -> MockCiphertext {
+        self.majorities.fetch_add(1, Ordering::Relaxed);
+        self.bootstraps.fetch_add(3, Ordering::Relaxed);
+        MockCiphertext::encrypt((a.value && b.value) || (c.value && (a.value || b.value)))
+    }
+}
@@ -0,0 +1,322 @@
+/*!
+On-disk keyset cache for development workflows.
+
+Generating keys takes seconds for most parameter sets, which adds up quickly
+in test suites and examples that create fresh keys on every run. A
+[`KeyCache`] stores generated keysets in a directory, one file per parameter
+set, keyed by the [`ParamsFingerprint`](crate::ParamsFingerprint) of the
+parameters. Every file carries a SHA-256 checksum, so truncated or corrupted
+entries are rejected instead of yielding broken keys.
+
+**Not for production secrets.** Cached client keys are stored unencrypted,
+and every process using the same directory shares the same keys. The cache
+is only available with the `key-cache` feature.
+
+## Access Control
+
+The checksum detects accidents, not tampering; keeping other local users
+away from the keys is the job of the file system. On Unix, the cache
+directory is created with mode `0700` and every cached keyset with mode
+`0600`. Loading or storing fails if the directory is a symbolic link, is
+owned by another user, or is accessible by group or others, so keys are
+neither leaked to nor planted by other users of a shared host.
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+use encircuit::KeyCache;
+# fn main() -> anyhow::Result<()> {
+let params = Params::for_scenario(Scenario::SafeAndBalanced)?;
+
+// Generated on the first run, loaded from disk afterwards
+let cache = KeyCache::in_user_cache_dir()?;
+let (client_key, server_key) = cache.get_or_generate(&params)?.split();
+# Ok(())
+# }
+```
+*/
+
+use crate::keys::{ClientKeyBytes, Keyset, ServerKeyBytes};
+use crate::params::Params;
+use crate::secret::SecretBytes;
+use anyhow::Result;
+use chacha20poly1305::aead::{OsRng, rand_core::RngCore};
+use sha2::{Digest, Sha256};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8; 4] = b"ENKC";
+const VERSION: u8 = 1;
+const EXTENSION: &str = "keyset";
+const CHECKSUM_LEN: usize = 32;
+/// Magic, version, parameters fingerprint and client key length.
+const HEADER_LEN: usize = MAGIC.len() + 1 + 32 + 8;
+
+/// A directory of cached keysets, keyed by parameters fingerprint.
+///
+/// **Development only**: client keys are stored unencrypted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCache {
+    dir: PathBuf,
+}
+
+impl KeyCache {
+    /// Use `dir` as cache directory.
+    ///
+    /// The directory is created when the first keyset is stored. It must be
+    /// private to the current user, see [Access Control](self#access-control).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Use the `encircuit/keys` directory in the user's cache directory.
+    ///
+    /// The cache directory is `$XDG_CACHE_HOME` or `$HOME/.cache`, or
+    /// `%LOCALAPPDATA%` on Windows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if none of these variables is set to an absolute path.
+    pub fn in_user_cache_dir() -> Result<Self> {
+        let absolute = |name: &str| std::env::var_os(name).map(PathBuf::from).filter(|path| path.is_absolute());
+        let base = absolute("XDG_CACHE_HOME")
+            .or_else(|| absolute("HOME").map(|home| home.join(".cache")))
+            .or_else(|| absolute("LOCALAPPDATA"))
+            .ok_or_else(|| anyhow::anyhow!("Cannot determine the user's cache directory"))?;
+        Ok(Self::new(base.join("encircuit").join("keys")))
+    }
+
+    /// Get the cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get the file a keyset for `params` is cached in.
+    pub fn path_for(&self, params: &Params) -> PathBuf {
+        self.dir.join(format!("{}.{}", params.fingerprint(), EXTENSION))
+    }
+
+    /// Load the cached keyset for `params`.
+    ///
+    /// Returns `None` if no keyset is cached for these parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory is not private to the current
+    /// user, or the file can't be read, fails its integrity check, or was
+    /// stored for other parameters.
+    pub fn load(&self, params: &Params) -> Result<Option<Keyset>> {
+        if !self.check_dir(false)? {
+            return Ok(None);
+        }
+        let path = self.path_for(params);
+        let data = match std::fs::read(&path) {
+            Ok(data) => SecretBytes::new(data),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => anyhow::bail!("Failed to read cached keyset {}: {}", path.display(), e),
+        };
+
+        decode(&data, params)
+            .map(Some)
+            .map_err(|e| anyhow::anyhow!("Invalid cached keyset {}: {}", path.display(), e))
+    }
+
+    /// Store a keyset generated with `params`, replacing any cached one.
+    ///
+    /// The file is written under a temporary name and renamed into place, so
+    /// concurrent readers never see a partially written keyset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keys belong to different keysets, the cache
+    /// directory is not private to the current user, or the keyset can't be
+    /// serialized or written.
+    pub fn store(&self, params: &Params, keyset: &Keyset) -> Result<PathBuf> {
+        let data = encode(params, keyset)?;
+        self.check_dir(true)?;
+
+        let path = self.path_for(params);
+        let mut suffix = [0u8; 8];
+        OsRng.fill_bytes(&mut suffix);
+        let suffix: String = suffix.iter().map(|byte| format!("{:02x}", byte)).collect();
+        let temporary = path.with_extension(format!("{}.{}.tmp", EXTENSION, suffix));
+
+        // `create_new` refuses to follow a file or link planted under the temporary name
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut created = false;
+        options
+            .open(&temporary)
+            .and_then(|mut file| {
+                created = true;
+                file.write_all(&data)?;
+                file.sync_all()
+            })
+            .and_then(|()| std::fs::rename(&temporary, &path))
+            .map_err(|e| {
+                if created {
+                    let _ = std::fs::remove_file(&temporary);
+                }
+                anyhow::anyhow!("Failed to write cached keyset {}: {}", path.display(), e)
+            })?;
+        Ok(path)
+    }
+
+    /// Load the cached keyset for `params`, generating and storing one if none is cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a cached keyset is invalid, the cache directory is
+    /// not private to the current user, or key generation or storing the new
+    /// keyset fails.
+    pub fn get_or_generate(&self, params: &Params) -> Result<Keyset> {
+        if let Some(keyset) = self.load(params)? {
+            return Ok(keyset);
+        }
+
+        let keyset = Keyset::generate(params)?;
+        self.store(params, &keyset)?;
+        Ok(keyset)
+    }
+
+    /// Remove the cached keyset for `params`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but can't be removed.
+    pub fn remove(&self, params: &Params) -> Result<()> {
+        let path = self.path_for(params);
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => {
+                anyhow::bail!("Failed to remove cached keyset {}: {}", path.display(), e)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Remove every cached keyset from the cache directory.
+    ///
+    /// Other files in the directory are left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory can't be listed or a file can't be removed.
+    pub fn clear(&self) -> Result<()> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => anyhow::bail!("Failed to list cache directory {}: {}", self.dir.display(), e),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == EXTENSION) {
+                std::fs::remove_file(&path)
+                    .map_err(|e| anyhow::anyhow!("Failed to remove cached keyset {}: {}", path.display(), e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the cache directory is private to the current user.
+    ///
+    /// Creates the directory if `create` is set. Returns whether it exists.
+    fn check_dir(&self, create: bool) -> Result<bool> {
+        let dir = &self.dir;
+        let metadata = match std::fs::symlink_metadata(dir) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == ErrorKind::NotFound && !create => return Ok(false),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                let mut builder = std::fs::DirBuilder::new();
+                builder.recursive(true);
+                #[cfg(unix)]
+                std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+                builder
+                    .create(dir)
+                    .and_then(|()| std::fs::symlink_metadata(dir))
+                    .map_err(|e| anyhow::anyhow!("Failed to create cache directory {}: {}", dir.display(), e))?
+            }
+            Err(e) => anyhow::bail!("Failed to access cache directory {}: {}", dir.display(), e),
+        };
+
+        if !metadata.is_dir() {
+            anyhow::bail!("Cache directory {} is not a directory or is a symbolic link", dir.display());
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            // SAFETY: geteuid has no preconditions and cannot fail
+            let user = unsafe { libc::geteuid() };
+            if metadata.uid() != user {
+                anyhow::bail!("Cache directory {} is owned by another user", dir.display());
+            }
+            if metadata.mode() & 0o077 != 0 {
+                anyhow::bail!(
+                    "Cache directory {} is accessible by other users (mode {:o}), it must have mode 700",
+                    dir.display(),
+                    metadata.mode() & 0o777
+                );
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Serialize a keyset with its parameters fingerprint and a checksum.
+fn encode(params: &Params, keyset: &Keyset) -> Result<SecretBytes> {
+    let client_key = keyset.client_key().as_bytes()?;
+    let server_key = keyset.server_key().as_bytes()?;
+
+    let mut data = SecretBytes::new(Vec::with_capacity(
+        HEADER_LEN + client_key.len() + server_key.len() + CHECKSUM_LEN,
+    ));
+    let buffer = data.buffer();
+    buffer.extend_from_slice(MAGIC);
+    buffer.push(VERSION);
+    buffer.extend_from_slice(params.fingerprint().as_bytes());
+    buffer.extend_from_slice(&(client_key.len() as u64).to_le_bytes());
+    buffer.extend_from_slice(&client_key);
+    buffer.extend_from_slice(&server_key);
+    let checksum = Sha256::digest(&buffer[..]);
+    buffer.extend_from_slice(&checksum);
+    Ok(data)
+}
+
+/// Check and deserialize a keyset produced by [`encode`].
+fn decode(data: &[u8], params: &Params) -> Result<Keyset> {
+    if data.len() < HEADER_LEN + CHECKSUM_LEN || &data[..MAGIC.len()] != MAGIC {
+        anyhow::bail!("not a keyset cache file");
+    }
+    let version = data[MAGIC.len()];
+    if version != VERSION {
+        anyhow::bail!("unsupported cache format version {}, expected {}", version, VERSION);
+    }
+
+    let (content, checksum) = data.split_at(data.len() - CHECKSUM_LEN);
+    if Sha256::digest(content)[..] != checksum[..] {
+        anyhow::bail!("checksum mismatch, the file is truncated or corrupted");
+    }
+
+    let fingerprint = &content[MAGIC.len() + 1..MAGIC.len() + 33];
+    if fingerprint != params.fingerprint().as_bytes() {
+        anyhow::bail!("the keyset was generated for other parameters");
+    }
+
+    let client_len = u64::from_le_bytes(content[HEADER_LEN - 8..HEADER_LEN].try_into().expect("8 bytes"));
+    let keys = &content[HEADER_LEN..];
+    let client_len = usize::try_from(client_len)
+        .ok()
+        .filter(|&len| len <= keys.len())
+        .ok_or_else(|| anyhow::anyhow!("client key length {} exceeds the file", client_len))?;
+    let (client_key, server_key) = keys.split_at(client_len);
+
+    let client_key = ClientKeyBytes::from_bytes(client_key.to_vec())?;
+    let server_key = ServerKeyBytes::from_bytes(server_key.to_vec())?;
+    Keyset::from_keys(client_key, server_key)
+}
@@ -8,7 +8,9 @@ The architecture uses extensible traits (Encryptable/Decryptable) to support
 future addition of integer types while maintaining a Boolean-focused core.
 */
 
-use crate::keys::ClientKeyBytes;
+use crate::deserialization::{self, DeserializationConfig};
+use crate::keys::{ClientKeyBytes, KeyId};
+use crate::params::Params;
 use anyhow::Result;
 
 #[cfg(feature = "serde")]
@@ -18,10 +20,15 @@ use serde::{Deserialize, Serialize};
 ///
 /// `BoolCt` wraps TFHE's Boolean ciphertext type and provides a clean
 /// interface for encryption, decryption, and Boolean operations.
+///
+/// Ciphertexts remember the [`KeyId`] of the keyset that encrypted them, so
+/// that evaluating or decrypting them with a key of another keyset fails
+/// instead of returning garbage.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BoolCt {
     ciphertext: tfhe::boolean::prelude::Ciphertext,
+    key_id: Option<KeyId>,
 }
 
 /// Trait for types that can be encrypted.
@@ -56,10 +63,9 @@ impl BoolCt {
     pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
         #[cfg(feature = "serde")]
         {
-            // Use bincode to deserialize the TFHE ciphertext directly
-            let ciphertext: tfhe::boolean::prelude::Ciphertext = bincode::deserialize(&data)
-                .map_err(|e| anyhow::anyhow!("Ciphertext deserialization failed: {}", e))?;
-            Ok(Self { ciphertext })
+            // Use bincode to deserialize the TFHE ciphertext and its key id
+            bincode::deserialize(&data)
+                .map_err(|e| anyhow::anyhow!("Ciphertext deserialization failed: {}", e))
         }
         #[cfg(not(feature = "serde"))]
         {
@@ -68,6 +74,45 @@ impl BoolCt {
         }
     }
 
+    /// Create a ciphertext from untrusted bytes, within the limits of `config`.
+    ///
+    /// Unlike `from_bytes`, this rejects data larger than the configured size
+    /// limit and, if the configuration names expected parameters, ciphertexts
+    /// that don't conform to them. Use it for data from untrusted sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - The data exceeds the size limit or cannot be deserialized
+    /// - The ciphertext doesn't conform to the expected parameters
+    pub fn safe_from_bytes(data: &[u8], config: &DeserializationConfig) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            let ciphertext: Self = config.deserialize(data, "ciphertext")?;
+            if let Some(params) = config.expected_params() {
+                ciphertext.check_conformance(params)?;
+            }
+            Ok(ciphertext)
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = (data, config); // Suppress unused warning
+            anyhow::bail!("Deserialization requires 'serde' feature to be enabled")
+        }
+    }
+
+    /// Check that the ciphertext was produced under `params`.
+    ///
+    /// Trivial ciphertexts carry no encryption and always conform.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ciphertext has another size than `params` produce.
+    pub fn check_conformance(&self, params: &Params) -> Result<()> {
+        deserialization::check_ciphertext(&self.ciphertext, params)
+    }
+
     /// Serialize the ciphertext to bytes using TFHE's native serialization.
     ///
     /// This uses bincode for efficient binary serialization of TFHE ciphertexts.
@@ -80,9 +125,9 @@ impl BoolCt {
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
         #[cfg(feature = "serde")]
         {
-            // Use bincode to serialize the TFHE ciphertext directly
+            // Use bincode to serialize the TFHE ciphertext with its key id
             // TFHE-rs implements Serialize for its ciphertext types
-            bincode::serialize(&self.ciphertext)
+            bincode::serialize(self)
                 .map_err(|e| anyhow::anyhow!("Ciphertext serialization failed: {}", e))
         }
         #[cfg(not(feature = "serde"))]
@@ -92,8 +137,14 @@ impl BoolCt {
     }
 
     /// Create a BoolCt from a TFHE ciphertext.
+    ///
+    /// The ciphertext has no key id, so it is never rejected as encrypted
+    /// under another keyset.
     pub fn from_tfhe_ciphertext(ciphertext: tfhe::boolean::prelude::Ciphertext) -> Self {
-        Self { ciphertext }
+        Self {
+            ciphertext,
+            key_id: None,
+        }
     }
 
     /// Get the underlying TFHE ciphertext.
@@ -101,6 +152,17 @@ impl BoolCt {
         &self.ciphertext
     }
 
+    /// Get the id of the keyset this ciphertext was encrypted under, if known.
+    pub fn key_id(&self) -> Option<KeyId> {
+        self.key_id
+    }
+
+    /// Set the id of the keyset this ciphertext was encrypted under.
+    pub(crate) fn with_key_id(mut self, key_id: Option<KeyId>) -> Self {
+        self.key_id = key_id;
+        self
+    }
+
 }
 
 impl Encryptable for bool {
@@ -109,7 +171,7 @@ impl Encryptable for bool {
     fn encrypt(&self, client_key: &ClientKeyBytes) -> Result<Self::Ciphertext> {
         let tfhe_key = client_key.tfhe_key()?;
         let ciphertext = tfhe_key.encrypt(*self);
-        Ok(BoolCt::from_tfhe_ciphertext(ciphertext))
+        Ok(BoolCt::from_tfhe_ciphertext(ciphertext).with_key_id(Some(client_key.id())))
     }
 }
 
@@ -117,6 +179,7 @@ impl Decryptable for BoolCt {
     type Plaintext = bool;
 
     fn decrypt(&self, client_key: &ClientKeyBytes) -> Result<Self::Plaintext> {
+        client_key.check_ciphertext(self)?;
         let tfhe_key = client_key.tfhe_key()?;
         Ok(tfhe_key.decrypt(&self.ciphertext))
     }
@@ -125,6 +188,7 @@ impl Decryptable for BoolCt {
 impl std::fmt::Debug for BoolCt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BoolCt")
+            .field("key_id", &self.key_id.map(|id| id.to_string()))
             .field("encrypted", &"<encrypted_data>")
             .finish()
     }
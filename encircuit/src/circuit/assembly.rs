@@ -0,0 +1,327 @@
+/*!
+Assembling circuit inputs from several contributors.
+
+When the inputs of a circuit belong to different parties, no single party can
+call [`Circuit::encrypt_inputs`]. Instead, every contributor encrypts the
+ports it owns into an [`InputContribution`], typically under a shared
+[`PublicKeyBytes`], and sends it to the server. The server merges the
+contributions with an [`InputAssembly`], which rejects unknown, duplicated
+and overlapping ports and refuses to finish while ports are missing.
+
+Ports are addressed by input index or by the label attached to the input
+node with [`CircuitBuilder::label`](crate::CircuitBuilder::label). Labels
+need not be unique; a label several inputs carry is rejected as ambiguous,
+and those inputs must be addressed by index.
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+use encircuit::{InputAssembly, InputContribution};
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, server_key) = Keyset::generate(&params)?.split();
+let mut builder = CircuitBuilder::default();
+let bid = builder.input();
+let reserve = builder.input();
+builder.label(bid, "bid");
+builder.label(reserve, "reserve");
+let out = builder.and(bid, reserve);
+let circuit = builder.finish(out);
+
+let public_key = client_key.public_key();
+
+// Each party encrypts only its own port
+let mut buyer = InputContribution::new("buyer");
+buyer.encrypt("bid", true, &public_key);
+let mut seller = InputContribution::new("seller");
+seller.encrypt("reserve", false, &public_key);
+
+// The server merges the submissions
+let mut assembly = InputAssembly::new(&circuit);
+assembly.add(buyer)?;
+assembly.add(seller)?;
+let result = assembly.finish()?.evaluate(&server_key);
+# Ok(())
+# }
+```
+*/
+
+use crate::{
+    ciphertext::BoolCt,
+    circuit::{Circuit, EncryptedCircuit, Gate, NodeId},
+    keys::PublicKeyBytes,
+};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An input port of a circuit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InputPort {
+    /// The input at this position, in the order inputs were added to the builder.
+    Index(usize),
+    /// The input carrying this label.
+    Name(String),
+}
+
+impl From<usize> for InputPort {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl From<&str> for InputPort {
+    fn from(name: &str) -> Self {
+        Self::Name(name.to_string())
+    }
+}
+
+impl From<String> for InputPort {
+    fn from(name: String) -> Self {
+        Self::Name(name)
+    }
+}
+
+impl fmt::Display for InputPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "input {}", index),
+            Self::Name(name) => write!(f, "input '{}'", name),
+        }
+    }
+}
+
+/// The encrypted inputs one contributor provides.
+///
+/// Ports are resolved against the circuit by the [`InputAssembly`], so
+/// contributors only need to know the names or indices of their ports.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InputContribution {
+    contributor: String,
+    inputs: Vec<(InputPort, BoolCt)>,
+}
+
+impl InputContribution {
+    /// Create an empty contribution.
+    ///
+    /// The contributor name only appears in error messages.
+    pub fn new(contributor: impl Into<String>) -> Self {
+        Self {
+            contributor: contributor.into(),
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Encrypt a value for a port with a public key.
+    pub fn encrypt(&mut self, port: impl Into<InputPort>, value: bool, public_key: &PublicKeyBytes) -> &mut Self {
+        self.insert(port, public_key.encrypt(value))
+    }
+
+    /// Provide an already encrypted value for a port.
+    pub fn insert(&mut self, port: impl Into<InputPort>, ciphertext: BoolCt) -> &mut Self {
+        self.inputs.push((port.into(), ciphertext));
+        self
+    }
+
+    /// Get the contributor name.
+    pub fn contributor(&self) -> &str {
+        &self.contributor
+    }
+
+    /// Get the ports this contribution provides, in insertion order.
+    pub fn ports(&self) -> impl Iterator<Item = &InputPort> {
+        self.inputs.iter().map(|(port, _)| port)
+    }
+
+    /// Create a contribution from bytes produced by [`InputContribution::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `serde` feature is not enabled or the data is invalid.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::deserialize(&data)
+                .map_err(|e| anyhow::anyhow!("Input contribution deserialization failed: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = data; // Suppress unused warning
+            anyhow::bail!("Deserialization requires 'serde' feature to be enabled")
+        }
+    }
+
+    /// Serialize the contribution to bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `serde` feature is not enabled or serialization fails.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::serialize(self)
+                .map_err(|e| anyhow::anyhow!("Input contribution serialization failed: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            anyhow::bail!("Serialization requires 'serde' feature to be enabled")
+        }
+    }
+}
+
+/// Merges [`InputContribution`]s into an evaluable [`EncryptedCircuit`].
+#[derive(Debug, Clone)]
+pub struct InputAssembly {
+    circuit: Circuit,
+    /// Input indices of every input label, more than one if the label is ambiguous.
+    names: HashMap<String, Vec<usize>>,
+    /// Node of every input, by input index.
+    nodes: Vec<NodeId>,
+    /// The ciphertext and contributor of every input provided so far.
+    slots: Vec<Option<(BoolCt, String)>>,
+}
+
+impl InputAssembly {
+    /// Start assembling the inputs of `circuit`.
+    pub fn new(circuit: &Circuit) -> Self {
+        let nodes: Vec<NodeId> = circuit
+            .gates()
+            .iter()
+            .enumerate()
+            .filter(|(_, gate)| matches!(gate, Gate::Input))
+            .map(|(node, _)| NodeId(node))
+            .collect();
+        // Labels need not be unique, so keep every input carrying one
+        let mut names: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, &node) in nodes.iter().enumerate() {
+            if let Some(label) = circuit.label(node) {
+                names.entry(label.to_string()).or_default().push(index);
+            }
+        }
+
+        Self {
+            circuit: circuit.clone(),
+            names,
+            slots: vec![None; nodes.len()],
+            nodes,
+        }
+    }
+
+    /// Merge a contribution.
+    ///
+    /// The contribution is merged entirely or not at all.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the contribution names a port the circuit doesn't
+    /// have or a label several inputs carry, provides the same input twice
+    /// (also through a name and an index resolving to the same input), or
+    /// provides an input another contribution already provided.
+    pub fn add(&mut self, contribution: InputContribution) -> Result<()> {
+        let InputContribution { contributor, inputs } = contribution;
+
+        let mut resolved: Vec<(usize, BoolCt)> = Vec::with_capacity(inputs.len());
+        for (port, ciphertext) in inputs {
+            let index = self.resolve(&port).map_err(|e| {
+                anyhow::anyhow!("Contribution from '{}' is invalid: {}", contributor, e)
+            })?;
+            if resolved.iter().any(|&(seen, _)| seen == index) {
+                anyhow::bail!(
+                    "Contribution from '{}' provides {} more than once",
+                    contributor,
+                    self.describe(index)
+                );
+            }
+            if let Some((_, owner)) = &self.slots[index] {
+                anyhow::bail!(
+                    "Contribution from '{}' overlaps with '{}' on {}",
+                    contributor,
+                    owner,
+                    self.describe(index)
+                );
+            }
+            resolved.push((index, ciphertext));
+        }
+
+        for (index, ciphertext) in resolved {
+            self.slots[index] = Some((ciphertext, contributor.clone()));
+        }
+        Ok(())
+    }
+
+    /// Get the indices of the inputs no contribution has provided yet.
+    pub fn missing_inputs(&self) -> Vec<usize> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.is_none())
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Check whether every input has been provided.
+    pub fn is_complete(&self) -> bool {
+        self.slots.iter().all(Option::is_some)
+    }
+
+    /// Build the encrypted circuit from the merged contributions.
+    ///
+    /// Public inputs, if any, are supplied afterwards with
+    /// [`EncryptedCircuit::with_public_inputs`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing the missing inputs if any input hasn't been provided.
+    pub fn finish(self) -> Result<EncryptedCircuit> {
+        let missing = self.missing_inputs();
+        if !missing.is_empty() {
+            let missing: Vec<String> = missing.into_iter().map(|index| self.describe(index)).collect();
+            anyhow::bail!("Missing inputs: {}", missing.join(", "));
+        }
+
+        let inputs = self
+            .slots
+            .into_iter()
+            .map(|slot| slot.expect("completeness checked above").0)
+            .collect();
+        Ok(EncryptedCircuit::new(self.circuit, inputs))
+    }
+
+    /// Map a port to its input index.
+    fn resolve(&self, port: &InputPort) -> Result<usize> {
+        match port {
+            InputPort::Index(index) if *index < self.slots.len() => Ok(*index),
+            InputPort::Index(index) => anyhow::bail!(
+                "input {} is out of range, the circuit has {} inputs",
+                index,
+                self.slots.len()
+            ),
+            InputPort::Name(name) => match self.names.get(name).map(Vec::as_slice) {
+                Some(&[index]) => Ok(index),
+                Some(indices) if !indices.is_empty() => {
+                    let indices: Vec<String> = indices.iter().map(ToString::to_string).collect();
+                    anyhow::bail!(
+                        "input label '{}' is ambiguous, it names inputs {}; address them by index",
+                        name,
+                        indices.join(", ")
+                    )
+                }
+                _ => anyhow::bail!("the circuit has no input labelled '{}'", name),
+            },
+        }
+    }
+
+    /// Describe an input by index and label, e.g. `input 1 ('reserve')`.
+    fn describe(&self, index: usize) -> String {
+        match self.circuit.label(self.nodes[index]) {
+            Some(label) => format!("input {} ('{}')", index, label),
+            None => format!("input {}", index),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+/*!
+Async evaluation for services built on Tokio.
+
+Homomorphic evaluation blocks its thread for seconds to hours, which would
+stall an async executor. [`EncryptedCircuit::evaluate_async`] offloads the
+work to Tokio's blocking thread pool and returns a future that resolves to
+the encrypted outputs.
+
+Dropping the future cancels the evaluation: it stops before the next gate
+and its thread is returned to the pool. The variant
+[`EncryptedCircuit::evaluate_async_with`] additionally takes a
+[`CancellationToken`] and a progress callback.
+
+Requires the `async` feature and must be called from within a Tokio runtime.
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+use std::sync::Arc;
+# async fn serve(encrypted: EncryptedCircuit, server_key: Arc<ServerKeyBytes>) -> anyhow::Result<()> {
+let result = encrypted.evaluate_async(server_key).await?;
+# Ok(())
+# }
+```
+*/
+
+use crate::{
+    ciphertext::BoolCt,
+    circuit::{CancellationToken, EncryptedCircuit, EvaluationProgress},
+    keys::ServerKeyBytes,
+};
+use anyhow::Result;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Cancels the token when dropped, i.e. when the evaluation future goes away.
+struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+impl EncryptedCircuit {
+    /// Evaluate the circuit on Tokio's blocking thread pool.
+    ///
+    /// The returned future owns everything it needs, so it can be spawned as
+    /// a task. Dropping it cancels the evaluation.
+    ///
+    /// # Errors
+    ///
+    /// The future resolves to an error if the server key is invalid, circuit
+    /// evaluation fails, or the blocking task panicked.
+    pub fn evaluate_async(
+        &self,
+        server_key: Arc<ServerKeyBytes>,
+    ) -> impl Future<Output = Result<Vec<BoolCt>>> + Send + 'static {
+        self.evaluate_async_with(server_key, CancellationToken::new(), |_| {})
+    }
+
+    /// Evaluate the circuit on Tokio's blocking thread pool with cancellation
+    /// and progress reporting.
+    ///
+    /// Cancelling `cancellation` or dropping the returned future stops the
+    /// evaluation before the next gate. `on_progress` is called on the
+    /// blocking thread after every gate.
+    ///
+    /// # Errors
+    ///
+    /// The future resolves to an error if the server key is invalid, the
+    /// evaluation was cancelled, circuit evaluation fails, or the blocking
+    /// task panicked.
+    pub fn evaluate_async_with<F>(
+        &self,
+        server_key: Arc<ServerKeyBytes>,
+        cancellation: CancellationToken,
+        on_progress: F,
+    ) -> impl Future<Output = Result<Vec<BoolCt>>> + Send + 'static
+    where
+        F: FnMut(EvaluationProgress) + Send + 'static,
+    {
+        let encrypted = self.clone();
+        let guard = CancelOnDrop(cancellation.clone());
+
+        async move {
+            let result = tokio::task::spawn_blocking(move || {
+                encrypted.try_evaluate_cancellable(&server_key, &cancellation, on_progress)
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!("Evaluation task failed: {}", e))?;
+
+            // The evaluation finished, so cancelling on drop is a no-op from here
+            drop(guard);
+            result
+        }
+    }
+}
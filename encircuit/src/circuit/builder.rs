@@ -5,15 +5,24 @@ This module provides the `CircuitBuilder` which allows users to construct
 Boolean circuits by adding gates and maintaining topological order.
 */
 
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Unique identifier for a node in the circuit.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct NodeId(pub(crate) usize);
 
 /// Types of gates supported in Boolean circuits.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Gate {
     /// Input gate - represents a circuit input
     Input,
+    /// Public input gate - represents a cleartext input known to the server
+    PublicInput,
     /// Constant gate - represents a constant Boolean value
     Constant(bool),
     /// AND gate with two inputs
@@ -26,6 +35,21 @@ pub enum Gate {
     Not(NodeId),
 }
 
+impl Gate {
+    /// Get the display name of this gate's type (e.g. `"AND"`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Gate::Input => "INPUT",
+            Gate::PublicInput => "PUBLIC_INPUT",
+            Gate::Constant(_) => "CONSTANT",
+            Gate::And(_, _) => "AND",
+            Gate::Or(_, _) => "OR",
+            Gate::Xor(_, _) => "XOR",
+            Gate::Not(_) => "NOT",
+        }
+    }
+}
+
 /// Builder for constructing Boolean circuits.
 ///
 /// The `CircuitBuilder` maintains gates in topological order to ensure
@@ -33,6 +57,7 @@ pub enum Gate {
 #[derive(Debug, Default)]
 pub struct CircuitBuilder {
     gates: Vec<Gate>,
+    labels: BTreeMap<usize, String>,
 }
 
 impl CircuitBuilder {
@@ -48,6 +73,22 @@ impl CircuitBuilder {
         self.add_gate(Gate::Input)
     }
 
+    /// Add a public (cleartext) input gate to the circuit.
+    ///
+    /// Public inputs are parameters known to the server, such as thresholds or
+    /// selectors. They are not encrypted by the client; their values are
+    /// supplied as plain `bool`s at evaluation time (see
+    /// [`EncryptedCircuit::with_public_inputs`]) and folded into the gates
+    /// that consume them, which saves both client-side encryption and
+    /// server-side bootstrapping.
+    ///
+    /// [`EncryptedCircuit::with_public_inputs`]: crate::EncryptedCircuit::with_public_inputs
+    ///
+    /// Returns the `NodeId` for this input.
+    pub fn public_input(&mut self) -> NodeId {
+        self.add_gate(Gate::PublicInput)
+    }
+
     /// Add a constant gate to the circuit.
     ///
     /// Returns the `NodeId` for this constant.
@@ -83,11 +124,22 @@ impl CircuitBuilder {
         self.add_gate(Gate::Not(input))
     }
 
+    /// Attach a label to a node.
+    ///
+    /// Labels are optional and carry no semantic meaning. They are used to group
+    /// gates in evaluation profiles, e.g. to see how much time the gates of an
+    /// adder take compared to a comparator. Labels on input nodes also name the
+    /// ports of an [`InputAssembly`](crate::InputAssembly). Labelling a node
+    /// twice replaces the previous label.
+    pub fn label(&mut self, node: NodeId, label: impl Into<String>) {
+        self.labels.insert(node.0, label.into());
+    }
+
     /// Finish building the circuit and return an immutable `Circuit`.
     ///
     /// The `output` parameter specifies which node represents the circuit's output.
     pub fn finish(self, output: NodeId) -> super::Circuit {
-        super::Circuit::new(self.gates, output)
+        super::Circuit::new(self.gates, output, self.labels)
     }
 
     /// Add a gate to the circuit and return its NodeId.
@@ -105,6 +157,14 @@ impl CircuitBuilder {
             .count()
     }
 
+    /// Get the number of public inputs in the circuit.
+    pub fn public_input_count(&self) -> usize {
+        self.gates
+            .iter()
+            .filter(|gate| matches!(gate, Gate::PublicInput))
+            .count()
+    }
+
     /// Get the total number of gates in the circuit.
     pub fn gate_count(&self) -> usize {
         self.gates.len()
@@ -0,0 +1,253 @@
+/*!
+Checkpointing for long-running circuit evaluations.
+
+Circuits with millions of gates can take hours to evaluate under the
+ultra-low-error parameter sets. An [`EvaluationCheckpoint`] captures the
+index of the next gate to evaluate together with every intermediate
+ciphertext that is still needed by the remaining gates, so an interrupted
+evaluation can be continued with [`EncryptedCircuit::resume_evaluation`].
+
+Checkpoints are self-contained: they carry the circuit structure and the
+encrypted (and public) inputs that have not been consumed yet, so only the
+server key is needed to resume.
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+use encircuit::EvaluationCheckpoint;
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, server_key) = Keyset::generate(&params)?.split();
+# let mut builder = CircuitBuilder::default();
+# let x = builder.input();
+# let y = builder.input();
+# let out = builder.and(x, y);
+# let circuit = builder.finish(out);
+let encrypted = circuit.encrypt_inputs(&[true, false], &client_key)?;
+
+// Persist a checkpoint every 10 000 gates
+let result = encrypted.try_evaluate_with_checkpoints(&server_key, 10_000, |checkpoint| {
+    std::fs::write("evaluation.ckpt", checkpoint.to_bytes()?)?;
+    Ok(())
+})?;
+
+// After a restart, continue from the last checkpoint
+let checkpoint = EvaluationCheckpoint::from_bytes(std::fs::read("evaluation.ckpt")?)?;
+let result = EncryptedCircuit::resume_evaluation(checkpoint, &server_key)?;
+# Ok(())
+# }
+```
+*/
+
+use crate::{
+    ciphertext::BoolCt,
+    circuit::{evaluator::Wire, Circuit, Gate},
+};
+use anyhow::Result;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of an in-progress homomorphic evaluation.
+///
+/// Produced periodically by [`EncryptedCircuit::try_evaluate_with_checkpoints`]
+/// and consumed by [`EncryptedCircuit::resume_evaluation`].
+///
+/// [`EncryptedCircuit::try_evaluate_with_checkpoints`]: crate::EncryptedCircuit::try_evaluate_with_checkpoints
+/// [`EncryptedCircuit::resume_evaluation`]: crate::EncryptedCircuit::resume_evaluation
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EvaluationCheckpoint {
+    circuit: Circuit,
+    next_gate: usize,
+    pending_inputs: Vec<BoolCt>,
+    pending_public_inputs: Vec<bool>,
+    live_values: Vec<(usize, Wire<BoolCt>)>,
+}
+
+impl EvaluationCheckpoint {
+    /// Create a new checkpoint.
+    ///
+    /// This is typically called by the evaluator in `EncryptedCircuit`.
+    pub(super) fn new(
+        circuit: Circuit,
+        next_gate: usize,
+        pending_inputs: Vec<BoolCt>,
+        pending_public_inputs: Vec<bool>,
+        live_values: Vec<(usize, Wire<BoolCt>)>,
+    ) -> Self {
+        Self {
+            circuit,
+            next_gate,
+            pending_inputs,
+            pending_public_inputs,
+            live_values,
+        }
+    }
+
+    /// Create a checkpoint from raw bytes.
+    ///
+    /// This deserializes a checkpoint that was previously serialized with [`to_bytes`].
+    ///
+    /// [`to_bytes`]: EvaluationCheckpoint::to_bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - The input data cannot be deserialized as a checkpoint
+    /// - The deserialized checkpoint is inconsistent with its circuit
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            let checkpoint: Self = bincode::deserialize(&data)
+                .map_err(|e| anyhow::anyhow!("Checkpoint deserialization failed: {}", e))?;
+            checkpoint.validate()?;
+            Ok(checkpoint)
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = data; // Suppress unused warning
+            anyhow::bail!("Deserialization requires 'serde' feature to be enabled")
+        }
+    }
+
+    /// Serialize the checkpoint to bytes.
+    ///
+    /// The returned bytes can be stored and later reconstructed using
+    /// [`EvaluationCheckpoint::from_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `serde` feature is not enabled or serialization fails.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::serialize(self)
+                .map_err(|e| anyhow::anyhow!("Checkpoint serialization failed: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            anyhow::bail!("Serialization requires 'serde' feature to be enabled")
+        }
+    }
+
+    /// Get the circuit being evaluated.
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// Replace the circuit without any validation, for testing that malformed checkpoints are rejected.
+    #[cfg(all(test, feature = "serde"))]
+    pub(crate) fn with_circuit_unchecked(mut self, circuit: Circuit) -> Self {
+        self.circuit = circuit;
+        self
+    }
+
+    /// Get the index of the next gate to evaluate.
+    pub fn next_gate(&self) -> usize {
+        self.next_gate
+    }
+
+    /// Get the fraction of gates already evaluated, between 0.0 and 1.0.
+    pub fn progress(&self) -> f64 {
+        if self.circuit.gate_count() == 0 {
+            return 1.0;
+        }
+        self.next_gate as f64 / self.circuit.gate_count() as f64
+    }
+
+    /// Get the number of intermediate values stored in this checkpoint.
+    ///
+    /// This includes values folded to constants, which are stored as plain bits.
+    pub fn live_value_count(&self) -> usize {
+        self.live_values.len()
+    }
+
+    /// Check that the checkpoint contains everything needed to resume.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the circuit is invalid, the gate index is out of
+    /// range, the number of pending (public) inputs is wrong, or an intermediate value
+    /// needed by the remaining gates is missing.
+    pub fn validate(&self) -> Result<()> {
+        self.circuit.validate()?;
+
+        let gates = self.circuit.gates();
+        if self.next_gate > gates.len() {
+            anyhow::bail!(
+                "Checkpoint gate index {} is out of bounds for a circuit with {} gates",
+                self.next_gate,
+                gates.len()
+            );
+        }
+
+        let remaining_inputs = gates[self.next_gate..]
+            .iter()
+            .filter(|gate| matches!(gate, Gate::Input))
+            .count();
+        if self.pending_inputs.len() != remaining_inputs {
+            anyhow::bail!(
+                "Checkpoint input count mismatch: expected {}, got {}",
+                remaining_inputs,
+                self.pending_inputs.len()
+            );
+        }
+
+        let remaining_public_inputs = gates[self.next_gate..]
+            .iter()
+            .filter(|gate| matches!(gate, Gate::PublicInput))
+            .count();
+        if self.pending_public_inputs.len() != remaining_public_inputs {
+            anyhow::bail!(
+                "Checkpoint public input count mismatch: expected {}, got {}",
+                remaining_public_inputs,
+                self.pending_public_inputs.len()
+            );
+        }
+
+        let mut available = vec![false; self.next_gate];
+        for (node, _) in &self.live_values {
+            if *node >= self.next_gate {
+                anyhow::bail!(
+                    "Checkpoint stores gate {} which has not been evaluated yet",
+                    node
+                );
+            }
+            available[*node] = true;
+        }
+
+        let require = |node: usize| -> Result<()> {
+            if node < self.next_gate && !available[node] {
+                anyhow::bail!("Checkpoint is missing the value of gate {}", node);
+            }
+            Ok(())
+        };
+
+        for gate in &gates[self.next_gate..] {
+            match gate {
+                Gate::And(left, right) | Gate::Or(left, right) | Gate::Xor(left, right) => {
+                    require(left.0)?;
+                    require(right.0)?;
+                }
+                Gate::Not(input) => require(input.0)?,
+                Gate::Input | Gate::PublicInput | Gate::Constant(_) => {}
+            }
+        }
+        require(self.circuit.output().0)
+    }
+
+    /// Split the checkpoint into its parts for resuming evaluation.
+    #[allow(clippy::type_complexity)]
+    pub(super) fn into_parts(self) -> (Circuit, usize, Vec<BoolCt>, Vec<bool>, Vec<(usize, Wire<BoolCt>)>) {
+        (
+            self.circuit,
+            self.next_gate,
+            self.pending_inputs,
+            self.pending_public_inputs,
+            self.live_values,
+        )
+    }
+}
@@ -6,28 +6,45 @@ ready for encryption and evaluation.
 */
 
 use crate::{
+    backend::Backend,
     ciphertext::Encryptable,
-    circuit::{Gate, NodeId},
-    keys::ClientKeyBytes,
+    circuit::{
+        evaluator::{self, EvaluationHooks, EvaluationState},
+        Gate, NodeId,
+    },
+    execution::ExecutionConfig,
+    keys::{ClientKeyBytes, PublicKeyBytes},
 };
 use anyhow::Result;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// An immutable Boolean circuit represented as a directed acyclic graph (DAG).
 ///
 /// The `Circuit` contains all gates in topological order and provides methods
 /// for encrypting inputs and preparing for evaluation.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Circuit {
     gates: Vec<Gate>,
     output: NodeId,
+    labels: BTreeMap<usize, String>,
 }
 
 impl Circuit {
     /// Create a new circuit from gates and output node.
     ///
     /// This is typically called by `CircuitBuilder::finish()`.
-    pub(super) fn new(gates: Vec<Gate>, output: NodeId) -> Self {
-        Self { gates, output }
+    pub(super) fn new(gates: Vec<Gate>, output: NodeId, labels: BTreeMap<usize, String>) -> Self {
+        Self { gates, output, labels }
+    }
+
+    /// Create a circuit without any validation, for testing that malformed circuits are rejected.
+    #[cfg(all(test, feature = "serde"))]
+    pub(crate) fn new_unchecked(gates: Vec<Gate>, output: NodeId) -> Self {
+        Self::new(gates, output, BTreeMap::new())
     }
 
     /// Encrypt the circuit inputs using the given client key.
@@ -44,6 +61,25 @@ impl Circuit {
         &self,
         inputs: &[bool],
         client_key: &ClientKeyBytes,
+    ) -> Result<super::EncryptedCircuit> {
+        self.encrypt_inputs_with_config(inputs, client_key, &ExecutionConfig::default())
+    }
+
+    /// Encrypt the circuit inputs on the threads selected by `config`.
+    ///
+    /// This is the same as [`Circuit::encrypt_inputs`], but lets the caller
+    /// cap or isolate the CPU used for encryption.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The number of inputs doesn't match the number of input gates
+    /// - Encryption fails
+    pub fn encrypt_inputs_with_config(
+        &self,
+        inputs: &[bool],
+        client_key: &ClientKeyBytes,
+        config: &ExecutionConfig,
     ) -> Result<super::EncryptedCircuit> {
         let input_count = self.count_inputs();
         if inputs.len() != input_count {
@@ -54,65 +90,182 @@ impl Circuit {
             );
         }
 
-        #[cfg(feature = "parallel")]
-        {
-            self.encrypt_inputs_parallel(inputs, client_key)
-        }
-        #[cfg(not(feature = "parallel"))]
-        {
-            self.encrypt_inputs_sequential(inputs, client_key)
-        }
+        let encrypted_inputs: Result<Vec<_>> = config
+            .map(inputs, |&input| {
+                // Use the actual client key for encryption
+                input.encrypt(client_key)
+            })
+            .into_iter()
+            .collect();
+
+        Ok(super::EncryptedCircuit::new(self.clone(), encrypted_inputs?))
     }
 
-    /// Encrypt inputs sequentially.
-    #[cfg(not(feature = "parallel"))]
-    fn encrypt_inputs_sequential(
+    /// Encrypt the circuit inputs deterministically from a seed.
+    ///
+    /// The same inputs, key and seed always produce byte-identical
+    /// ciphertexts. Encryption runs on the calling thread. **Insecure**: only
+    /// meant for reproducible tests. Only available with the
+    /// `insecure-deterministic-rng` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The number of inputs doesn't match the number of input gates
+    /// - Encryption fails
+    #[cfg(feature = "insecure-deterministic-rng")]
+    pub fn encrypt_inputs_from_seed(
         &self,
         inputs: &[bool],
         client_key: &ClientKeyBytes,
+        seed: [u8; 32],
     ) -> Result<super::EncryptedCircuit> {
-        let mut encrypted_inputs = Vec::new();
+        crate::deterministic::with_seeded_engine(seed, || {
+            self.encrypt_inputs_with_config(inputs, client_key, &ExecutionConfig::sequential())
+        })
+    }
 
-        for &input in inputs {
-            // Use the actual client key for encryption
-            let encrypted = input.encrypt(client_key)?;
-            encrypted_inputs.push(encrypted);
+    /// Encrypt the circuit inputs using a public key.
+    ///
+    /// This lets data producers encrypt inputs without holding the client
+    /// key. The result is evaluated and decrypted like inputs encrypted with
+    /// [`Circuit::encrypt_inputs`]. Public-key encryption is considerably
+    /// slower than client-key encryption; inputs are encrypted in parallel
+    /// when the `parallel` feature is enabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of inputs doesn't match the number of
+    /// input gates.
+    pub fn encrypt_inputs_with_public_key(
+        &self,
+        inputs: &[bool],
+        public_key: &PublicKeyBytes,
+    ) -> Result<super::EncryptedCircuit> {
+        let input_count = self.count_inputs();
+        if inputs.len() != input_count {
+            anyhow::bail!(
+                "Input count mismatch: expected {}, got {}",
+                input_count,
+                inputs.len()
+            );
         }
 
-        // Pre-encrypt Boolean constants for use during evaluation
-        let encrypted_false = false.encrypt(client_key)?;
-        let encrypted_true = true.encrypt(client_key)?;
+        let encrypted_inputs = ExecutionConfig::default().map(inputs, |&input| public_key.encrypt(input));
+        Ok(super::EncryptedCircuit::new(self.clone(), encrypted_inputs))
+    }
 
-        Ok(super::EncryptedCircuit::new(self.clone(), encrypted_inputs, encrypted_false, encrypted_true))
+    /// Evaluate the circuit on an arbitrary [`Backend`].
+    ///
+    /// This runs the same scheduling code used for homomorphic evaluation with
+    /// any gate implementation, e.g. [`PlaintextBackend`] for a reference
+    /// simulation or [`MockBackend`] for fast tests.
+    ///
+    /// [`PlaintextBackend`]: crate::PlaintextBackend
+    /// [`MockBackend`]: crate::MockBackend
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - The backend evaluating individual gates
+    /// * `key` - The backend's evaluation key
+    /// * `inputs` - One value per input gate, in input order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of inputs doesn't match the number of
+    /// input gates or evaluation fails.
+    pub fn evaluate_with<B: Backend>(
+        &self,
+        backend: &B,
+        key: &B::Key,
+        inputs: &[B::Ciphertext],
+    ) -> Result<Vec<B::Ciphertext>> {
+        self.evaluate_with_public_inputs(backend, key, inputs, &[])
     }
 
-    /// Encrypt inputs in parallel using rayon.
-    #[cfg(feature = "parallel")]
-    fn encrypt_inputs_parallel(
+    /// Evaluate a circuit with public inputs on an arbitrary [`Backend`].
+    ///
+    /// Like [`Circuit::evaluate_with`], with one cleartext value per public
+    /// input gate. Public values are folded into the gates that consume them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of inputs or public inputs doesn't match
+    /// the circuit, or evaluation fails.
+    pub fn evaluate_with_public_inputs<B: Backend>(
         &self,
-        inputs: &[bool],
-        client_key: &ClientKeyBytes,
-    ) -> Result<super::EncryptedCircuit> {
-        use rayon::prelude::*;
+        backend: &B,
+        key: &B::Key,
+        inputs: &[B::Ciphertext],
+        public_inputs: &[bool],
+    ) -> Result<Vec<B::Ciphertext>> {
+        let input_count = self.count_inputs();
+        if inputs.len() != input_count {
+            anyhow::bail!(
+                "Input count mismatch: expected {}, got {}",
+                input_count,
+                inputs.len()
+            );
+        }
+        self.check_public_inputs(public_inputs)?;
 
-        let encrypted_inputs: Result<Vec<_>> = inputs
-            .par_iter()
-            .map(|&input| {
-                // Use the actual client key for encryption
-                input.encrypt(client_key)
-            })
-            .collect();
+        let state = EvaluationState::new(self.gates.len());
+        evaluator::evaluate_from(backend, key, self, inputs, public_inputs, state, EvaluationHooks::default())
+    }
+
+    /// Evaluate a circuit on an arbitrary [`Backend`] using the threads
+    /// selected by `config`.
+    ///
+    /// With a sequential configuration this is the same as
+    /// [`Circuit::evaluate_with_public_inputs`]. Otherwise the circuit is
+    /// evaluated level by level, and the independent gates of each level run
+    /// concurrently.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of inputs or public inputs doesn't match
+    /// the circuit, or evaluation fails.
+    pub fn evaluate_with_config<B>(
+        &self,
+        backend: &B,
+        key: &B::Key,
+        inputs: &[B::Ciphertext],
+        public_inputs: &[bool],
+        config: &ExecutionConfig,
+    ) -> Result<Vec<B::Ciphertext>>
+    where
+        B: Backend + Sync,
+        B::Ciphertext: Send + Sync,
+        B::Key: Sync,
+    {
+        if config.is_sequential() {
+            return self.evaluate_with_public_inputs(backend, key, inputs, public_inputs);
+        }
+
+        let input_count = self.count_inputs();
+        if inputs.len() != input_count {
+            anyhow::bail!(
+                "Input count mismatch: expected {}, got {}",
+                input_count,
+                inputs.len()
+            );
+        }
+        self.check_public_inputs(public_inputs)?;
 
-        // Pre-encrypt Boolean constants for use during evaluation
-        let encrypted_false = false.encrypt(client_key)?;
-        let encrypted_true = true.encrypt(client_key)?;
+        evaluator::evaluate_parallel(backend, key, self, inputs, public_inputs, config)
+    }
 
-        Ok(super::EncryptedCircuit::new(
-            self.clone(),
-            encrypted_inputs?,
-            encrypted_false,
-            encrypted_true,
-        ))
+    /// Check that the number of public input values matches the circuit.
+    pub(super) fn check_public_inputs(&self, public_inputs: &[bool]) -> Result<()> {
+        let public_input_count = self.public_input_count();
+        if public_inputs.len() != public_input_count {
+            anyhow::bail!(
+                "Public input count mismatch: expected {}, got {}",
+                public_input_count,
+                public_inputs.len()
+            );
+        }
+        Ok(())
     }
 
     /// Count the number of input gates in the circuit.
@@ -133,16 +286,72 @@ impl Circuit {
         self.output
     }
 
+    /// Compute, for every node, the index of the last gate that reads it.
+    ///
+    /// Nodes that are never read get their own index, so their value can be
+    /// released as soon as it is produced. The output node is kept alive until
+    /// the end of evaluation and maps to `gate_count()`.
+    pub(super) fn last_uses(&self) -> Vec<usize> {
+        let mut last_uses: Vec<usize> = (0..self.gates.len()).collect();
+
+        for (i, gate) in self.gates.iter().enumerate() {
+            match gate {
+                Gate::And(left, right) | Gate::Or(left, right) | Gate::Xor(left, right) => {
+                    last_uses[left.0] = last_uses[left.0].max(i);
+                    last_uses[right.0] = last_uses[right.0].max(i);
+                }
+                Gate::Not(input) => {
+                    last_uses[input.0] = last_uses[input.0].max(i);
+                }
+                Gate::Input | Gate::PublicInput | Gate::Constant(_) => {}
+            }
+        }
+
+        if let Some(last_use) = last_uses.get_mut(self.output.0) {
+            *last_use = self.gates.len();
+        }
+
+        last_uses
+    }
+
+    /// Get the label attached to a node with [`CircuitBuilder::label`], if any.
+    ///
+    /// [`CircuitBuilder::label`]: crate::CircuitBuilder::label
+    pub fn label(&self, node: NodeId) -> Option<&str> {
+        self.labels.get(&node.0).map(String::as_str)
+    }
+
     /// Get the total number of gates in the circuit.
     pub fn gate_count(&self) -> usize {
         self.gates.len()
     }
 
     /// Get the number of input gates in the circuit.
+    ///
+    /// Only encrypted inputs are counted; see [`Circuit::public_input_count`]
+    /// for the inputs supplied in cleartext at evaluation time.
     pub fn input_count(&self) -> usize {
         self.count_inputs()
     }
 
+    /// Get the number of public (cleartext) input gates in the circuit.
+    pub fn public_input_count(&self) -> usize {
+        self.gates
+            .iter()
+            .filter(|gate| matches!(gate, Gate::PublicInput))
+            .count()
+    }
+
+    /// Get the number of bootstraps a TFHE evaluation performs.
+    ///
+    /// Every AND, OR and XOR gate costs one bootstrap; NOT gates are free.
+    /// Gates folded away by public inputs or constants may lower the actual
+    /// count, so this is an upper bound.
+    pub fn bootstrap_count(&self) -> usize {
+        let stats = self.stats();
+        stats.and_gates + stats.or_gates + stats.xor_gates
+    }
+
     /// Get statistics about the circuit.
     pub fn stats(&self) -> CircuitStats {
         let mut stats = CircuitStats::default();
@@ -150,6 +359,7 @@ impl Circuit {
         for gate in &self.gates {
             match gate {
                 Gate::Input => stats.inputs += 1,
+                Gate::PublicInput => stats.public_inputs += 1,
                 Gate::Constant(_) => stats.constants += 1,
                 Gate::And(_, _) => stats.and_gates += 1,
                 Gate::Or(_, _) => stats.or_gates += 1,
@@ -173,9 +383,9 @@ impl Circuit {
     ///
     /// Returns an error if the circuit is invalid.
     pub fn validate(&self) -> Result<()> {
-        // Check for cycles using DFS
-        if self.has_cycles() {
-            anyhow::bail!("Circuit contains cycles");
+        // Check that output node is valid
+        if self.output.0 >= self.gates.len() {
+            anyhow::bail!("Output node {} is out of bounds", self.output.0);
         }
 
         // Check that all node references are valid
@@ -194,15 +404,16 @@ impl Circuit {
                         anyhow::bail!("Gate {} references future gate {}", i, input.0);
                     }
                 }
-                Gate::Input | Gate::Constant(_) => {
+                Gate::Input | Gate::PublicInput | Gate::Constant(_) => {
                     // These gates don't reference other gates
                 }
             }
         }
 
-        // Check that output node is valid
-        if self.output.0 >= self.gates.len() {
-            anyhow::bail!("Output node {} is out of bounds", self.output.0);
+        // Check for cycles using DFS, which indexes gates without bounds
+        // checks and so must run after the references are known to be valid
+        if self.has_cycles() {
+            anyhow::bail!("Circuit contains cycles");
         }
 
         Ok(())
@@ -246,7 +457,7 @@ impl Circuit {
                     return true;
                 }
             }
-            Gate::Input | Gate::Constant(_) => {
+            Gate::Input | Gate::PublicInput | Gate::Constant(_) => {
                 // Leaf nodes - no dependencies
             }
         }
@@ -261,12 +472,21 @@ impl Circuit {
     /// which determines the minimum number of sequential operations
     /// required for evaluation.
     pub fn depth(&self) -> usize {
+        self.levels()[self.output.0]
+    }
+
+    /// Calculate the level of every gate.
+    ///
+    /// The level of a gate is the length of the longest path from any input
+    /// or constant to that gate. Inputs and constants are at level 0; gates
+    /// on the same level do not depend on each other.
+    pub fn levels(&self) -> Vec<usize> {
         let mut depths = vec![0; self.gates.len()];
 
         // Calculate depth for each gate
         for (i, gate) in self.gates.iter().enumerate() {
             depths[i] = match gate {
-                Gate::Input | Gate::Constant(_) => 0,
+                Gate::Input | Gate::PublicInput | Gate::Constant(_) => 0,
                 Gate::Not(input) => depths[input.0] + 1,
                 Gate::And(left, right) | Gate::Or(left, right) | Gate::Xor(left, right) => {
                     std::cmp::max(depths[left.0], depths[right.0]) + 1
@@ -274,7 +494,7 @@ impl Circuit {
             };
         }
 
-        depths[self.output.0]
+        depths
     }
 
     /// Get a complexity estimate for this circuit.
@@ -310,6 +530,8 @@ impl Circuit {
 pub struct CircuitStats {
     /// Number of input gates
     pub inputs: usize,
+    /// Number of public input gates
+    pub public_inputs: usize,
     /// Number of constant gates
     pub constants: usize,
     /// Number of AND gates
@@ -0,0 +1,86 @@
+/*!
+Cancellation and progress reporting for long-running evaluations.
+
+A [`CancellationToken`] is shared between the code driving an evaluation and
+the evaluation itself; cancelling it stops the evaluation before the next
+gate. Progress is reported after every gate as an [`EvaluationProgress`].
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+use encircuit::CancellationToken;
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, server_key) = Keyset::generate(&params)?.split();
+# let mut builder = CircuitBuilder::default();
+# let x = builder.input();
+# let y = builder.input();
+# let out = builder.and(x, y);
+# let circuit = builder.finish(out);
+let encrypted = circuit.encrypt_inputs(&[true, false], &client_key)?;
+
+let token = CancellationToken::new();
+let watchdog = token.clone(); // e.g. handed to a request timeout handler
+
+let result = encrypted.try_evaluate_cancellable(&server_key, &token, |progress| {
+    println!("{:.0}% done", progress.fraction() * 100.0);
+});
+# Ok(())
+# }
+```
+*/
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A shared flag requesting an evaluation to stop.
+///
+/// Clones share the same flag, so any clone can cancel the evaluation.
+/// Cancellation is checked between gates: the gate being evaluated when the
+/// token is cancelled still completes.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Progress of a running evaluation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvaluationProgress {
+    /// Number of gates evaluated so far.
+    pub completed_gates: usize,
+    /// Number of gates in the circuit.
+    pub total_gates: usize,
+}
+
+impl EvaluationProgress {
+    /// Get the fraction of gates evaluated, between 0.0 and 1.0.
+    pub fn fraction(&self) -> f64 {
+        if self.total_gates == 0 {
+            return 1.0;
+        }
+        self.completed_gates as f64 / self.total_gates as f64
+    }
+
+    /// Check whether every gate has been evaluated.
+    pub fn is_complete(&self) -> bool {
+        self.completed_gates >= self.total_gates
+    }
+}
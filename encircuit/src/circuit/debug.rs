@@ -0,0 +1,247 @@
+/*!
+Debug evaluation with a client-key oracle.
+
+When a homomorphic result disagrees with expectations, it is hard to tell
+whether the circuit is wrong or a bootstrap failed. The debug evaluator
+answers that question: it takes the client key as well, decrypts the result
+of every gate right after it was computed, and compares it against a
+plaintext simulation of the same [`Circuit`] on the decrypted inputs. The
+first gate whose decrypted value differs from the simulation is reported as
+a [`GateDivergence`].
+
+If no gate diverges but the output is still unexpected, the circuit itself
+computes something other than intended.
+
+**For development only.** The evaluator needs the client key, so it must
+never run on an untrusted server. It is only available with the
+`debug-oracle` feature.
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, server_key) = Keyset::generate(&params)?.split();
+# let mut builder = CircuitBuilder::default();
+# let x = builder.input();
+# let y = builder.input();
+# let out = builder.and(x, y);
+# let circuit = builder.finish(out);
+let encrypted = circuit.encrypt_inputs(&[true, false], &client_key)?;
+let report = encrypted.try_evaluate_debug(&server_key, &client_key)?;
+
+match report.divergence() {
+    Some(divergence) => println!("Bootstrap failure: {}", divergence),
+    None => println!("All {} gates match the simulation", report.checked_gates()),
+}
+# Ok(())
+# }
+```
+*/
+
+use crate::{
+    backend::{Backend, PlaintextBackend, TfheBackend},
+    ciphertext::{BoolCt, Decryptable},
+    circuit::{
+        evaluator::{self, EvaluationHooks, EvaluationState, Wire},
+        Circuit, EncryptedCircuit, Gate, NodeId,
+    },
+    keys::{ClientKeyBytes, ServerKeyBytes},
+};
+use anyhow::Result;
+use std::fmt;
+
+/// The first gate whose decrypted result differs from the plaintext simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateDivergence {
+    /// The diverging node.
+    pub node: NodeId,
+    /// The gate type name (e.g. `"AND"`).
+    pub gate_type: &'static str,
+    /// The level of the gate in the circuit (see [`Circuit::levels`]).
+    pub level: usize,
+    /// The label attached to the node, if any.
+    pub label: Option<String>,
+    /// The value computed by the plaintext simulation.
+    pub expected: bool,
+    /// The decrypted value of the evaluated gate.
+    pub actual: bool,
+}
+
+impl fmt::Display for GateDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} gate {} at level {}", self.gate_type, self.node.id(), self.level)?;
+        if let Some(label) = &self.label {
+            write!(f, " ({})", label)?;
+        }
+        write!(f, " decrypted to {}, expected {}", self.actual, self.expected)
+    }
+}
+
+/// The result of a debug evaluation.
+#[derive(Debug, Clone)]
+pub struct DebugReport<C = BoolCt> {
+    outputs: Vec<C>,
+    divergence: Option<GateDivergence>,
+    checked_gates: usize,
+}
+
+impl<C> DebugReport<C> {
+    /// Get the circuit outputs, exactly as a regular evaluation returns them.
+    pub fn outputs(&self) -> &[C] {
+        &self.outputs
+    }
+
+    /// Take the circuit outputs.
+    pub fn into_outputs(self) -> Vec<C> {
+        self.outputs
+    }
+
+    /// Get the first gate that diverged from the plaintext simulation, if any.
+    pub fn divergence(&self) -> Option<&GateDivergence> {
+        self.divergence.as_ref()
+    }
+
+    /// Check whether every gate matched the plaintext simulation.
+    pub fn is_consistent(&self) -> bool {
+        self.divergence.is_none()
+    }
+
+    /// Get the number of gates compared against the simulation.
+    pub fn checked_gates(&self) -> usize {
+        self.checked_gates
+    }
+}
+
+impl Circuit {
+    /// Evaluate the circuit on `backend`, checking every gate with an oracle.
+    ///
+    /// `decrypt` reveals the plaintext of a backend value. The inputs are
+    /// decrypted to run a plaintext simulation, and the result of every gate
+    /// is decrypted and compared against it. Evaluation continues after a
+    /// divergence, so the report always contains the outputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of inputs or public inputs doesn't match
+    /// the circuit, or evaluation or decryption fails.
+    pub fn evaluate_debug_with<B, D>(
+        &self,
+        backend: &B,
+        key: &B::Key,
+        inputs: &[B::Ciphertext],
+        public_inputs: &[bool],
+        decrypt: D,
+    ) -> Result<DebugReport<B::Ciphertext>>
+    where
+        B: Backend,
+        D: Fn(&B::Ciphertext) -> Result<bool>,
+    {
+        let plain_inputs = inputs.iter().map(&decrypt).collect::<Result<Vec<_>>>()?;
+        let expected = self.simulate(&plain_inputs, public_inputs)?;
+        let levels = self.levels();
+
+        let mut divergence = None;
+        let mut checked_gates = 0;
+        let mut observer = |gate_index: usize, value: &Wire<B::Ciphertext>| -> Result<()> {
+            let actual = match value {
+                Wire::Known(value) => *value,
+                Wire::Cipher(ct) => decrypt(ct)?,
+            };
+            checked_gates += 1;
+
+            if divergence.is_none() && actual != expected[gate_index] {
+                let node = NodeId(gate_index);
+                divergence = Some(GateDivergence {
+                    node,
+                    gate_type: self.gates()[gate_index].name(),
+                    level: levels[gate_index],
+                    label: self.label(node).map(str::to_string),
+                    expected: expected[gate_index],
+                    actual,
+                });
+            }
+            Ok(())
+        };
+
+        let hooks = EvaluationHooks {
+            observer: Some(&mut observer),
+            ..EvaluationHooks::default()
+        };
+        let state = EvaluationState::new(self.gate_count());
+        let outputs = evaluator::evaluate_from(backend, key, self, inputs, public_inputs, state, hooks)?;
+
+        Ok(DebugReport {
+            outputs,
+            divergence,
+            checked_gates,
+        })
+    }
+
+    /// Compute the plaintext value of every gate.
+    fn simulate(&self, inputs: &[bool], public_inputs: &[bool]) -> Result<Vec<bool>> {
+        let input_count = self.input_count();
+        if inputs.len() != input_count {
+            anyhow::bail!(
+                "Input count mismatch: expected {}, got {}",
+                input_count,
+                inputs.len()
+            );
+        }
+        self.check_public_inputs(public_inputs)?;
+
+        let mut inputs = inputs.iter();
+        let mut public_inputs = public_inputs.iter();
+        let mut values: Vec<Option<Wire<bool>>> = vec![None; self.gate_count()];
+        let mut expected = Vec::with_capacity(self.gate_count());
+        for (gate_index, gate) in self.gates().iter().enumerate() {
+            let value = match gate {
+                Gate::Input => *inputs.next().expect("input count checked above"),
+                Gate::PublicInput => *public_inputs.next().expect("public input count checked above"),
+                _ => match evaluator::evaluate_gate(&PlaintextBackend, &(), gate, &values)? {
+                    Wire::Known(value) | Wire::Cipher(value) => value,
+                },
+            };
+            values[gate_index] = Some(Wire::Known(value));
+            expected.push(value);
+        }
+
+        Ok(expected)
+    }
+}
+
+impl EncryptedCircuit {
+    /// Evaluate the circuit homomorphically, checking every gate with the client key.
+    ///
+    /// The result of every gate is decrypted and compared against a plaintext
+    /// simulation of the circuit on the decrypted inputs; the first mismatch
+    /// is reported in the returned [`DebugReport`].
+    ///
+    /// **Development only**: never give the client key to an untrusted server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either key is invalid or belongs to another
+    /// keyset than the inputs, the public inputs are missing, or evaluation
+    /// or decryption fails.
+    pub fn try_evaluate_debug(
+        &self,
+        server_key: &ServerKeyBytes,
+        client_key: &ClientKeyBytes,
+    ) -> Result<DebugReport> {
+        let tfhe_server_key = server_key.tfhe_key()
+            .map_err(|e| anyhow::anyhow!("Invalid server key: {}", e))?;
+        self.check_key(server_key)?;
+
+        self.circuit()
+            .evaluate_debug_with(
+                &TfheBackend,
+                tfhe_server_key,
+                self.encrypted_inputs(),
+                self.public_inputs(),
+                |ct| ct.decrypt(client_key),
+            )
+            .map_err(|e| anyhow::anyhow!("Debug evaluation failed: {}", e))
+    }
+}
@@ -4,42 +4,136 @@ Encrypted circuit evaluation.
 This module provides the `EncryptedCircuit` type which represents a circuit
 with encrypted inputs, ready for homomorphic evaluation.
 
-The `EncryptedCircuit` pre-encrypts Boolean constants (`true` and `false`) 
-during construction to ensure they are available during server-side evaluation,
-where the client key is not accessible.
+Gates are evaluated by the [`TfheBackend`] through the backend-independent
+evaluator. Boolean constants are handled entirely on the server: they are
+folded into the gates that consume them, and only materialized as trivial
+ciphertexts when they reach an output. The client therefore only encrypts
+the actual circuit inputs.
+
+Public inputs (see [`CircuitBuilder::public_input`]) are supplied in
+cleartext with [`EncryptedCircuit::with_public_inputs`] and folded the same
+way as constants.
+
+[`CircuitBuilder::public_input`]: crate::CircuitBuilder::public_input
 */
 
 use crate::{
+    backend::TfheBackend,
     ciphertext::BoolCt,
-    circuit::{Circuit, Gate},
-    keys::ServerKeyBytes,
+    circuit::{
+        evaluator::{self, EvaluationHooks, EvaluationState, Wire},
+        profile::Profiler,
+        CancellationToken, Circuit, EvaluationCheckpoint, EvaluationProfile, EvaluationProgress,
+    },
+    execution::ExecutionConfig,
+    keys::{KeyId, ServerKeyBytes},
 };
 use anyhow::Result;
 
-use tfhe::boolean::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Checkpoint interval and callback receiving each checkpoint.
+type CheckpointCallback<'a> = (usize, &'a mut dyn FnMut(&EvaluationCheckpoint) -> Result<()>);
 
 /// A circuit with encrypted inputs, ready for homomorphic evaluation.
 ///
-/// The `EncryptedCircuit` contains the circuit structure along with encrypted
-/// inputs and pre-encrypted Boolean constants for evaluation.
+/// The `EncryptedCircuit` contains the circuit structure along with the
+/// encrypted inputs for evaluation, and the cleartext values of its public
+/// inputs, if any.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EncryptedCircuit {
     circuit: Circuit,
     encrypted_inputs: Vec<BoolCt>,
-    encrypted_false: BoolCt,
-    encrypted_true: BoolCt,
+    public_inputs: Vec<bool>,
 }
 
 impl EncryptedCircuit {
     /// Create a new encrypted circuit.
     ///
     /// This is typically called by `Circuit::encrypt_inputs()`.
-    pub(super) fn new(circuit: Circuit, encrypted_inputs: Vec<BoolCt>, encrypted_false: BoolCt, encrypted_true: BoolCt) -> Self {
+    pub(super) fn new(circuit: Circuit, encrypted_inputs: Vec<BoolCt>) -> Self {
         Self {
             circuit,
             encrypted_inputs,
-            encrypted_false,
-            encrypted_true,
+            public_inputs: Vec::new(),
+        }
+    }
+
+    /// Supply the values of the circuit's public inputs.
+    ///
+    /// Public inputs are known to the server, so they are passed as plain
+    /// `bool`s in the order their gates were added with
+    /// [`CircuitBuilder::public_input`]. Every evaluation method requires the
+    /// public inputs to be set when the circuit has any.
+    ///
+    /// [`CircuitBuilder::public_input`]: crate::CircuitBuilder::public_input
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of values doesn't match the number of
+    /// public inputs in the circuit.
+    pub fn with_public_inputs(mut self, public_inputs: &[bool]) -> Result<Self> {
+        self.circuit.check_public_inputs(public_inputs)?;
+        self.public_inputs = public_inputs.to_vec();
+        Ok(self)
+    }
+
+    /// Create an encrypted circuit from bytes produced by [`EncryptedCircuit::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - The data is invalid
+    /// - The circuit is malformed, e.g. a gate references a node out of range
+    /// - The number of inputs or public inputs doesn't match the circuit
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            let encrypted: Self = bincode::deserialize(&data)
+                .map_err(|e| anyhow::anyhow!("Encrypted circuit deserialization failed: {}", e))?;
+            encrypted
+                .circuit
+                .validate()
+                .map_err(|e| anyhow::anyhow!("Invalid encrypted circuit: {}", e))?;
+            if encrypted.encrypted_inputs.len() != encrypted.circuit.input_count() {
+                anyhow::bail!(
+                    "Input count mismatch: expected {}, got {}",
+                    encrypted.circuit.input_count(),
+                    encrypted.encrypted_inputs.len()
+                );
+            }
+            if !encrypted.public_inputs.is_empty() {
+                encrypted.circuit.check_public_inputs(&encrypted.public_inputs)?;
+            }
+            Ok(encrypted)
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = data; // Suppress unused warning
+            anyhow::bail!("Deserialization requires 'serde' feature to be enabled")
+        }
+    }
+
+    /// Serialize the encrypted circuit to bytes.
+    ///
+    /// The ciphertexts keep their [`KeyId`], so evaluating the deserialized
+    /// circuit with a server key of another keyset still fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `serde` feature is not enabled or serialization fails.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::serialize(self)
+                .map_err(|e| anyhow::anyhow!("Encrypted circuit serialization failed: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            anyhow::bail!("Serialization requires 'serde' feature to be enabled")
         }
     }
 
@@ -49,8 +143,15 @@ impl EncryptedCircuit {
     /// the inputs or intermediate values.
     ///
     /// Returns a vector containing the encrypted outputs of the circuit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an input was encrypted under another keyset than the server
+    /// key, or if evaluation fails. Use [`EncryptedCircuit::try_evaluate`] to
+    /// get an error instead.
     pub fn evaluate(&self, server_key: &ServerKeyBytes) -> Vec<BoolCt> {
         let tfhe_server_key = server_key.tfhe_key().expect("Failed to get TFHE server key");
+        self.check_key(server_key).expect("Key mismatch");
         self.evaluate_with_tfhe_key(tfhe_server_key).expect("Evaluation failed")
     }
 
@@ -63,16 +164,83 @@ impl EncryptedCircuit {
     ///
     /// Returns an error if:
     /// - The server key is invalid
+    /// - An input was encrypted under another keyset than the server key
     /// - Circuit evaluation fails
     /// - Memory allocation fails during evaluation
     pub fn try_evaluate(&self, server_key: &ServerKeyBytes) -> Result<Vec<BoolCt>> {
         let tfhe_server_key = server_key.tfhe_key()
             .map_err(|e| anyhow::anyhow!("Invalid server key: {}", e))?;
+        self.check_key(server_key)?;
         
         self.evaluate_with_tfhe_key(tfhe_server_key)
             .map_err(|e| anyhow::anyhow!("Circuit evaluation failed: {}", e))
     }
 
+    /// Evaluate the circuit on the threads selected by `config`.
+    ///
+    /// Unlike [`EncryptedCircuit::try_evaluate`], which evaluates one gate
+    /// after the other on the calling thread, a parallel configuration
+    /// evaluates the independent gates of each circuit level concurrently.
+    /// Use a dedicated pool (see [`ExecutionConfig::threads`]) to cap the CPU
+    /// used by one request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server key is invalid or circuit evaluation fails.
+    pub fn try_evaluate_with_config(
+        &self,
+        server_key: &ServerKeyBytes,
+        config: &ExecutionConfig,
+    ) -> Result<Vec<BoolCt>> {
+        let tfhe_server_key = server_key.tfhe_key()
+            .map_err(|e| anyhow::anyhow!("Invalid server key: {}", e))?;
+        self.check_key(server_key)?;
+
+        if config.is_sequential() {
+            return self.evaluate_with_tfhe_key(tfhe_server_key)
+                .map_err(|e| anyhow::anyhow!("Circuit evaluation failed: {}", e));
+        }
+
+        evaluator::evaluate_parallel(
+            &TfheBackend,
+            tfhe_server_key,
+            &self.circuit,
+            &self.encrypted_inputs,
+            &self.public_inputs,
+            config,
+        )
+        .map_err(|e| anyhow::anyhow!("Circuit evaluation failed: {}", e))
+    }
+
+    /// Evaluate a batch of encrypted circuits on the threads selected by `config`.
+    ///
+    /// The circuits are distributed over the threads, and each circuit is
+    /// evaluated gate by gate. The results are returned in batch order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server key is invalid or the evaluation of any
+    /// circuit fails; the error names the failing batch entry.
+    pub fn try_evaluate_batch(
+        batch: &[EncryptedCircuit],
+        server_key: &ServerKeyBytes,
+        config: &ExecutionConfig,
+    ) -> Result<Vec<Vec<BoolCt>>> {
+        let tfhe_server_key = server_key.tfhe_key()
+            .map_err(|e| anyhow::anyhow!("Invalid server key: {}", e))?;
+
+        let indexed: Vec<(usize, &EncryptedCircuit)> = batch.iter().enumerate().collect();
+        config
+            .map(&indexed, |(index, encrypted)| {
+                encrypted.check_key(server_key)
+                    .map_err(|e| anyhow::anyhow!("Batch entry {} is invalid: {}", index, e))?;
+                encrypted.evaluate_with_tfhe_key(tfhe_server_key)
+                    .map_err(|e| anyhow::anyhow!("Circuit evaluation failed for batch entry {}: {}", index, e))
+            })
+            .into_iter()
+            .collect()
+    }
+
     /// Evaluate with timeout protection.
     ///
     /// Attempts to evaluate the circuit with a timeout to prevent indefinite blocking.
@@ -108,100 +276,249 @@ impl EncryptedCircuit {
         }
     }
 
-    fn evaluate_with_tfhe_key(&self, server_key: &tfhe::boolean::prelude::ServerKey) -> anyhow::Result<Vec<BoolCt>> {
-        // Use NodeId directly as index into gates - no mapping needed
-        let mut gate_results: Vec<Option<BoolCt>> = vec![None; self.circuit.gates().len()];
-        let mut input_index = 0;
-
-        // Walk through gates in topological order
-        for (gate_index, gate) in self.circuit.gates().iter().enumerate() {
-            let result = match gate {
-                Gate::Input => {
-                    // Use the encrypted input
-                    let encrypted_input = self.encrypted_inputs[input_index].clone();
-                    input_index += 1;
-                    encrypted_input
-                }
-                Gate::Constant(value) => {
-                    // Use the pre-encrypted constants
-                    if *value {
-                        self.encrypted_true.clone()
-                    } else {
-                        self.encrypted_false.clone()
-                    }
-                }
-                Gate::And(left, right) => {
-                    let left_val = gate_results[left.0]
-                        .as_ref()
-                        .ok_or_else(|| anyhow::anyhow!("Left input gate {} not yet computed", left.0))?;
-                    let right_val = gate_results[right.0]
-                        .as_ref()
-                        .ok_or_else(|| anyhow::anyhow!("Right input gate {} not yet computed", right.0))?;
-                    
-                    self.homomorphic_and(left_val, right_val, server_key)
-                }
-                Gate::Or(left, right) => {
-                    let left_val = gate_results[left.0]
-                        .as_ref()
-                        .ok_or_else(|| anyhow::anyhow!("Left input gate {} not yet computed", left.0))?;
-                    let right_val = gate_results[right.0]
-                        .as_ref()
-                        .ok_or_else(|| anyhow::anyhow!("Right input gate {} not yet computed", right.0))?;
-                    
-                    self.homomorphic_or(left_val, right_val, server_key)
-                }
-                Gate::Xor(left, right) => {
-                    let left_val = gate_results[left.0]
-                        .as_ref()
-                        .ok_or_else(|| anyhow::anyhow!("Left input gate {} not yet computed", left.0))?;
-                    let right_val = gate_results[right.0]
-                        .as_ref()
-                        .ok_or_else(|| anyhow::anyhow!("Right input gate {} not yet computed", right.0))?;
-                    
-                    self.homomorphic_xor(left_val, right_val, server_key)
-                }
-                Gate::Not(input) => {
-                    let input_val = gate_results[input.0]
-                        .as_ref()
-                        .ok_or_else(|| anyhow::anyhow!("Input gate {} not yet computed", input.0))?;
-                    
-                    self.homomorphic_not(input_val, server_key)
-                }
-            };
-
-            gate_results[gate_index] = Some(result);
+    /// Evaluate the circuit with cancellation and progress reporting.
+    ///
+    /// `cancellation` is checked before every gate; once it is cancelled the
+    /// evaluation stops and returns an error. `on_progress` is called after
+    /// every gate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server key is invalid, the evaluation was
+    /// cancelled, or circuit evaluation fails.
+    pub fn try_evaluate_cancellable<F>(
+        &self,
+        server_key: &ServerKeyBytes,
+        cancellation: &CancellationToken,
+        mut on_progress: F,
+    ) -> Result<Vec<BoolCt>>
+    where
+        F: FnMut(EvaluationProgress),
+    {
+        let tfhe_server_key = server_key.tfhe_key()
+            .map_err(|e| anyhow::anyhow!("Invalid server key: {}", e))?;
+        self.check_key(server_key)?;
+
+        let total_gates = self.circuit.gate_count();
+        let mut observer = |gate_index: usize, _: &Wire<BoolCt>| -> Result<()> {
+            on_progress(EvaluationProgress {
+                completed_gates: gate_index + 1,
+                total_gates,
+            });
+            if cancellation.is_cancelled() {
+                anyhow::bail!("Evaluation cancelled after {} of {} gates", gate_index + 1, total_gates);
+            }
+            Ok(())
+        };
+
+        if cancellation.is_cancelled() {
+            anyhow::bail!("Evaluation cancelled before it started");
         }
 
-        // Return the output value(s)
-        let output_result = gate_results[self.circuit.output().0]
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Output gate {} not computed", self.circuit.output().0))?;
-        
-        Ok(vec![output_result.clone()])
+        let hooks = EvaluationHooks {
+            observer: Some(&mut observer),
+            ..EvaluationHooks::default()
+        };
+        let state = EvaluationState::new(total_gates);
+        evaluator::evaluate_from(
+            &TfheBackend,
+            tfhe_server_key,
+            &self.circuit,
+            &self.encrypted_inputs,
+            &self.public_inputs,
+            state,
+            hooks,
+        )
+    }
+
+    /// Evaluate the circuit and hand out a checkpoint every `interval` gates.
+    ///
+    /// Each checkpoint contains the index of the next gate plus every
+    /// intermediate ciphertext still needed by the remaining gates. Persist it
+    /// from `on_checkpoint` (for example with [`EvaluationCheckpoint::to_bytes`])
+    /// to be able to continue with [`EncryptedCircuit::resume_evaluation`]
+    /// after a restart.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_key` - The server key for homomorphic operations
+    /// * `interval` - Number of gates evaluated between two checkpoints
+    /// * `on_checkpoint` - Callback receiving each checkpoint
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - `interval` is zero
+    /// - Circuit evaluation fails
+    /// - `on_checkpoint` returns an error, which aborts the evaluation
+    pub fn try_evaluate_with_checkpoints<F>(
+        &self,
+        server_key: &ServerKeyBytes,
+        interval: usize,
+        mut on_checkpoint: F,
+    ) -> Result<Vec<BoolCt>>
+    where
+        F: FnMut(&EvaluationCheckpoint) -> Result<()>,
+    {
+        if interval == 0 {
+            anyhow::bail!("Checkpoint interval must be greater than zero");
+        }
+
+        let tfhe_server_key = server_key.tfhe_key()
+            .map_err(|e| anyhow::anyhow!("Invalid server key: {}", e))?;
+        self.check_key(server_key)?;
+        let state = EvaluationState::new(self.circuit.gate_count());
+
+        self.evaluate_from(state, tfhe_server_key, Some((interval, &mut on_checkpoint)), None)
+            .map_err(|e| anyhow::anyhow!("Circuit evaluation failed: {}", e))
     }
 
-    /// Perform homomorphic AND operation.
-    fn homomorphic_and(&self, left: &BoolCt, right: &BoolCt, server_key: &tfhe::boolean::prelude::ServerKey) -> BoolCt {
-        let result = server_key.and(left.tfhe_ciphertext(), right.tfhe_ciphertext());
-        BoolCt::from_tfhe_ciphertext(result)
+    /// Continue an evaluation from a previously saved checkpoint.
+    ///
+    /// The checkpoint is self-contained, so only the server key used for the
+    /// original evaluation is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint is inconsistent or evaluation fails.
+    pub fn resume_evaluation(
+        checkpoint: EvaluationCheckpoint,
+        server_key: &ServerKeyBytes,
+    ) -> Result<Vec<BoolCt>> {
+        Self::resume_from(checkpoint, server_key, None)
     }
 
-    /// Perform homomorphic OR operation.
-    fn homomorphic_or(&self, left: &BoolCt, right: &BoolCt, server_key: &tfhe::boolean::prelude::ServerKey) -> BoolCt {
-        let result = server_key.or(left.tfhe_ciphertext(), right.tfhe_ciphertext());
-        BoolCt::from_tfhe_ciphertext(result)
+    /// Continue an evaluation from a checkpoint, producing further checkpoints.
+    ///
+    /// This combines [`EncryptedCircuit::resume_evaluation`] with the periodic
+    /// checkpointing of [`EncryptedCircuit::try_evaluate_with_checkpoints`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `interval` is zero, the checkpoint is inconsistent,
+    /// evaluation fails, or `on_checkpoint` returns an error.
+    pub fn resume_evaluation_with_checkpoints<F>(
+        checkpoint: EvaluationCheckpoint,
+        server_key: &ServerKeyBytes,
+        interval: usize,
+        mut on_checkpoint: F,
+    ) -> Result<Vec<BoolCt>>
+    where
+        F: FnMut(&EvaluationCheckpoint) -> Result<()>,
+    {
+        if interval == 0 {
+            anyhow::bail!("Checkpoint interval must be greater than zero");
+        }
+
+        Self::resume_from(checkpoint, server_key, Some((interval, &mut on_checkpoint)))
     }
 
-    /// Perform homomorphic XOR operation.
-    fn homomorphic_xor(&self, left: &BoolCt, right: &BoolCt, server_key: &tfhe::boolean::prelude::ServerKey) -> BoolCt {
-        let result = server_key.xor(left.tfhe_ciphertext(), right.tfhe_ciphertext());
-        BoolCt::from_tfhe_ciphertext(result)
+    /// Rebuild the evaluation state stored in a checkpoint and run the remaining gates.
+    fn resume_from(
+        checkpoint: EvaluationCheckpoint,
+        server_key: &ServerKeyBytes,
+        checkpoints: Option<CheckpointCallback<'_>>,
+    ) -> Result<Vec<BoolCt>> {
+        checkpoint.validate()
+            .map_err(|e| anyhow::anyhow!("Invalid checkpoint: {}", e))?;
+
+        let tfhe_server_key = server_key.tfhe_key()
+            .map_err(|e| anyhow::anyhow!("Invalid server key: {}", e))?;
+
+        let (circuit, next_gate, pending_inputs, pending_public_inputs, live_values) =
+            checkpoint.into_parts();
+        let mut state = EvaluationState::new(circuit.gate_count());
+        state.next_gate = next_gate;
+        for (node, value) in live_values {
+            if let Wire::Cipher(ct) = &value {
+                server_key.check_ciphertext(ct, || format!("Checkpoint value of node {}", node))?;
+            }
+            state.values[node] = Some(value);
+        }
+
+        // The pending inputs become the inputs of a circuit that starts at `next_gate`
+        let mut encrypted = Self::new(circuit, pending_inputs);
+        encrypted.public_inputs = pending_public_inputs;
+        encrypted.check_key(server_key)?;
+        encrypted.evaluate_from(state, tfhe_server_key, checkpoints, None)
+            .map_err(|e| anyhow::anyhow!("Circuit evaluation failed: {}", e))
     }
 
-    /// Perform homomorphic NOT operation.
-    fn homomorphic_not(&self, input: &BoolCt, server_key: &tfhe::boolean::prelude::ServerKey) -> BoolCt {
-        let result = server_key.not(input.tfhe_ciphertext());
-        BoolCt::from_tfhe_ciphertext(result)
+    /// Evaluate the circuit while recording the wall time of every gate.
+    ///
+    /// Profiling adds a small constant overhead per gate, so it is opt-in.
+    /// The returned [`EvaluationProfile`] can be summarized by gate type,
+    /// label or level, printed as a table, or exported as Chrome trace JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server key is invalid or circuit evaluation fails.
+    pub fn try_evaluate_profiled(&self, server_key: &ServerKeyBytes) -> Result<(Vec<BoolCt>, EvaluationProfile)> {
+        let tfhe_server_key = server_key.tfhe_key()
+            .map_err(|e| anyhow::anyhow!("Invalid server key: {}", e))?;
+        self.check_key(server_key)?;
+
+        let mut profiler = Profiler::new(&self.circuit);
+        let state = EvaluationState::new(self.circuit.gate_count());
+
+        let outputs = self.evaluate_from(state, tfhe_server_key, None, Some(&mut profiler))
+            .map_err(|e| anyhow::anyhow!("Circuit evaluation failed: {}", e))?;
+        Ok((outputs, profiler.finish()))
+    }
+
+    /// Check that every encrypted input was encrypted under the server key's keyset.
+    pub(crate) fn check_key(&self, server_key: &ServerKeyBytes) -> Result<()> {
+        for (index, ciphertext) in self.encrypted_inputs.iter().enumerate() {
+            server_key.check_ciphertext(ciphertext, || format!("Ciphertext for input {}", index))?;
+        }
+        Ok(())
+    }
+
+    fn evaluate_with_tfhe_key(&self, server_key: &tfhe::boolean::prelude::ServerKey) -> anyhow::Result<Vec<BoolCt>> {
+        let state = EvaluationState::new(self.circuit.gate_count());
+        self.evaluate_from(state, server_key, None, None)
+    }
+
+    /// Evaluate the remaining gates on the TFHE backend, starting from the given state.
+    fn evaluate_from(
+        &self,
+        state: EvaluationState<BoolCt>,
+        server_key: &tfhe::boolean::prelude::ServerKey,
+        checkpoints: Option<CheckpointCallback<'_>>,
+        profiler: Option<&mut Profiler>,
+    ) -> Result<Vec<BoolCt>> {
+        // Turn raw evaluation states into self-contained checkpoints
+        let mut checkpoint_hook = checkpoints.map(|(interval, on_checkpoint)| {
+            (interval, move |state: &EvaluationState<BoolCt>| on_checkpoint(&self.checkpoint(state)))
+        });
+        let hooks = EvaluationHooks {
+            checkpoints: checkpoint_hook
+                .as_mut()
+                .map(|(interval, hook)| (*interval, hook as &mut dyn FnMut(&EvaluationState<BoolCt>) -> Result<()>)),
+            profiler,
+            observer: None,
+        };
+
+        evaluator::evaluate_from(
+            &TfheBackend,
+            server_key,
+            &self.circuit,
+            &self.encrypted_inputs,
+            &self.public_inputs,
+            state,
+            hooks,
+        )
+    }
+
+    /// Capture the given evaluation state as a self-contained checkpoint.
+    fn checkpoint(&self, state: &EvaluationState<BoolCt>) -> EvaluationCheckpoint {
+        EvaluationCheckpoint::new(
+            self.circuit.clone(),
+            state.next_gate,
+            self.encrypted_inputs[state.next_input..].to_vec(),
+            self.public_inputs[state.next_public_input..].to_vec(),
+            state.live_values(),
+        )
     }
 
     /// Get the underlying circuit.
@@ -209,6 +526,13 @@ impl EncryptedCircuit {
         &self.circuit
     }
 
+    /// Replace the circuit without any validation, for testing that malformed encrypted circuits are rejected.
+    #[cfg(all(test, feature = "serde"))]
+    pub(crate) fn with_circuit_unchecked(mut self, circuit: Circuit) -> Self {
+        self.circuit = circuit;
+        self
+    }
+
     /// Get the encrypted inputs.
     pub fn encrypted_inputs(&self) -> &[BoolCt] {
         &self.encrypted_inputs
@@ -219,18 +543,15 @@ impl EncryptedCircuit {
         self.encrypted_inputs.len()
     }
 
-    /// Get a reference to the encrypted false constant.
-    pub fn encrypted_false(&self) -> &BoolCt {
-        &self.encrypted_false
+    /// Get the public input values supplied with [`EncryptedCircuit::with_public_inputs`].
+    pub fn public_inputs(&self) -> &[bool] {
+        &self.public_inputs
     }
 
-    /// Get a reference to the encrypted true constant.
-    pub fn encrypted_true(&self) -> &BoolCt {
-        &self.encrypted_true
-    }
-
-    /// Get references to both encrypted constants as a tuple (false, true).
-    pub fn encrypted_constants(&self) -> (&BoolCt, &BoolCt) {
-        (&self.encrypted_false, &self.encrypted_true)
+    /// Get the id of the keyset the inputs were encrypted under.
+    ///
+    /// Returns `None` if no input carries a key id.
+    pub fn key_id(&self) -> Option<KeyId> {
+        self.encrypted_inputs.iter().find_map(BoolCt::key_id)
     }
 }
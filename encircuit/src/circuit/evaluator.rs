@@ -0,0 +1,410 @@
+/*!
+Backend-independent circuit evaluation.
+
+This module contains the gate scheduling loop shared by every [`Backend`]:
+it walks the gates in topological order, feeds inputs, releases intermediate
+values once their last consumer has run, and drives optional instrumentation
+such as checkpointing and profiling.
+
+Constants never leave the server as ciphertexts: wires carrying a value known
+to the evaluator are folded into the gates that consume them (e.g. `x AND
+true` becomes `x`, `x XOR true` becomes `NOT x`). A constant is only
+materialized through [`Backend::constant`] when it reaches a circuit output.
+
+Besides the gate-by-gate loop, [`evaluate_parallel`] evaluates the circuit
+level by level, running the independent gates of each level on the threads
+of an [`ExecutionConfig`].
+*/
+
+use crate::{
+    backend::Backend,
+    circuit::{profile::Profiler, Circuit, Gate, NodeId},
+    execution::ExecutionConfig,
+};
+use anyhow::Result;
+use std::time::Instant;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The value carried by a circuit wire during evaluation.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub(crate) enum Wire<C> {
+    /// A value known to the evaluator, e.g. the result of a constant gate.
+    Known(bool),
+    /// A backend ciphertext.
+    Cipher(C),
+}
+
+impl<C: Clone> Wire<C> {
+    /// Turn the wire into a backend ciphertext, materializing known values.
+    pub(crate) fn into_ciphertext<B: Backend<Ciphertext = C>>(self, backend: &B, key: &B::Key) -> C {
+        match self {
+            Wire::Known(value) => backend.constant(key, value),
+            Wire::Cipher(ct) => ct,
+        }
+    }
+}
+
+/// Callback invoked with the index and value of every evaluated gate.
+pub(crate) type GateObserver<'a, C> = &'a mut dyn FnMut(usize, &Wire<C>) -> Result<()>;
+
+/// Checkpoint interval and callback invoked with the evaluation state.
+pub(crate) type CheckpointHook<'a, C> = (usize, &'a mut dyn FnMut(&EvaluationState<C>) -> Result<()>);
+
+/// Optional instrumentation attached to an evaluation.
+pub(crate) struct EvaluationHooks<'a, C> {
+    /// Periodic checkpointing.
+    pub(crate) checkpoints: Option<CheckpointHook<'a, C>>,
+    /// Per-gate wall time recording.
+    pub(crate) profiler: Option<&'a mut Profiler>,
+    /// Inspection of every gate result, e.g. by a debug oracle.
+    pub(crate) observer: Option<GateObserver<'a, C>>,
+}
+
+impl<C> Default for EvaluationHooks<'_, C> {
+    fn default() -> Self {
+        Self {
+            checkpoints: None,
+            profiler: None,
+            observer: None,
+        }
+    }
+}
+
+/// Mutable state of an in-progress evaluation.
+pub(crate) struct EvaluationState<C> {
+    /// Index of the next gate to evaluate.
+    pub(crate) next_gate: usize,
+    /// Index of the next unconsumed input.
+    pub(crate) next_input: usize,
+    /// Index of the next unconsumed public input.
+    pub(crate) next_public_input: usize,
+    /// Live gate results, indexed by `NodeId`.
+    pub(crate) values: Vec<Option<Wire<C>>>,
+}
+
+impl<C: Clone> EvaluationState<C> {
+    /// Create the state for a fresh evaluation of `gate_count` gates.
+    pub(crate) fn new(gate_count: usize) -> Self {
+        Self {
+            next_gate: 0,
+            next_input: 0,
+            next_public_input: 0,
+            values: vec![None; gate_count],
+        }
+    }
+
+    /// Get the live values together with their node index.
+    pub(crate) fn live_values(&self) -> Vec<(usize, Wire<C>)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(node, value)| value.as_ref().map(|ct| (node, ct.clone())))
+            .collect()
+    }
+
+    /// Drop the values whose last consumer is the gate at `gate_index`.
+    fn release_dead_values(&mut self, gate: &Gate, gate_index: usize, last_uses: &[usize]) {
+        match gate {
+            Gate::And(left, right) | Gate::Or(left, right) | Gate::Xor(left, right) => {
+                for operand in [left.0, right.0] {
+                    if last_uses[operand] == gate_index {
+                        self.values[operand] = None;
+                    }
+                }
+            }
+            Gate::Not(input) => {
+                if last_uses[input.0] == gate_index {
+                    self.values[input.0] = None;
+                }
+            }
+            Gate::Input | Gate::PublicInput | Gate::Constant(_) => {}
+        }
+
+        if last_uses[gate_index] == gate_index {
+            self.values[gate_index] = None;
+        }
+    }
+}
+
+/// Look up an already computed operand.
+fn operand<'v, C>(values: &'v [Option<Wire<C>>], node: NodeId, role: &str) -> Result<&'v Wire<C>> {
+    values[node.0]
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("{} input gate {} not yet computed", role, node.0))
+}
+
+/// Evaluate the remaining gates of `circuit` starting from the given state.
+///
+/// `inputs` and `public_inputs` hold the values for the (public) input gates
+/// that have not been consumed yet, starting at `state.next_input` and
+/// `state.next_public_input` respectively. Public inputs are treated like
+/// constants and folded into the gates that consume them. Intermediate values
+/// are released as soon as their last consumer has been evaluated, which keeps
+/// memory usage (and checkpoints) proportional to the circuit width rather
+/// than its size.
+pub(crate) fn evaluate_from<B: Backend>(
+    backend: &B,
+    key: &B::Key,
+    circuit: &Circuit,
+    inputs: &[B::Ciphertext],
+    public_inputs: &[bool],
+    mut state: EvaluationState<B::Ciphertext>,
+    mut hooks: EvaluationHooks<'_, B::Ciphertext>,
+) -> Result<Vec<B::Ciphertext>> {
+    let gates = circuit.gates();
+    let last_uses = circuit.last_uses();
+
+    // Walk through gates in topological order
+    while state.next_gate < gates.len() {
+        let gate_index = state.next_gate;
+        let gate = &gates[gate_index];
+        let values = &state.values;
+        let started = Instant::now();
+
+        let result = match gate {
+            Gate::Input => {
+                let input = inputs
+                    .get(state.next_input)
+                    .ok_or_else(|| anyhow::anyhow!("Missing input for gate {}", gate_index))?
+                    .clone();
+                state.next_input += 1;
+                Wire::Cipher(input)
+            }
+            Gate::PublicInput => {
+                let input = *public_inputs
+                    .get(state.next_public_input)
+                    .ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "Missing public input for gate {} (public inputs must be supplied before evaluation)",
+                            gate_index
+                        )
+                    })?;
+                state.next_public_input += 1;
+                Wire::Known(input)
+            }
+            _ => evaluate_gate(backend, key, gate, values)?,
+        };
+
+        if let Some(profiler) = hooks.profiler.as_deref_mut() {
+            profiler.record(circuit, gate, gate_index, started, started.elapsed());
+        }
+
+        if let Some(observer) = hooks.observer.as_mut() {
+            observer(gate_index, &result)?;
+        }
+
+        state.values[gate_index] = Some(result);
+        state.release_dead_values(gate, gate_index, &last_uses);
+        state.next_gate += 1;
+
+        if let Some((interval, on_checkpoint)) = hooks.checkpoints.as_mut()
+            && state.next_gate.is_multiple_of(*interval)
+            && state.next_gate < gates.len()
+        {
+            on_checkpoint(&state)?;
+        }
+    }
+
+    // Return the output value(s)
+    let output = circuit.output();
+    let output_result = state.values[output.0]
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("Output gate {} not computed", output.0))?;
+
+    Ok(vec![output_result.into_ciphertext(backend, key)])
+}
+
+/// Evaluate the circuit level by level on the threads selected by `config`.
+///
+/// All gates of one level only depend on gates of lower levels, so they are
+/// evaluated concurrently. Values are released once the last level consuming
+/// them has run.
+pub(crate) fn evaluate_parallel<B>(
+    backend: &B,
+    key: &B::Key,
+    circuit: &Circuit,
+    inputs: &[B::Ciphertext],
+    public_inputs: &[bool],
+    config: &ExecutionConfig,
+) -> Result<Vec<B::Ciphertext>>
+where
+    B: Backend + Sync,
+    B::Ciphertext: Send + Sync,
+    B::Key: Sync,
+{
+    let gates = circuit.gates();
+    let levels = circuit.levels();
+    let depth = levels.iter().copied().max().unwrap_or(0);
+
+    // Group gates by level; inputs and constants are fed directly
+    let mut state = EvaluationState::new(gates.len());
+    let mut by_level: Vec<Vec<usize>> = vec![Vec::new(); depth + 1];
+    for (gate_index, gate) in gates.iter().enumerate() {
+        match gate {
+            Gate::Input => {
+                let input = inputs
+                    .get(state.next_input)
+                    .ok_or_else(|| anyhow::anyhow!("Missing input for gate {}", gate_index))?
+                    .clone();
+                state.next_input += 1;
+                state.values[gate_index] = Some(Wire::Cipher(input));
+            }
+            Gate::PublicInput => {
+                let input = *public_inputs.get(state.next_public_input).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Missing public input for gate {} (public inputs must be supplied before evaluation)",
+                        gate_index
+                    )
+                })?;
+                state.next_public_input += 1;
+                state.values[gate_index] = Some(Wire::Known(input));
+            }
+            Gate::Constant(value) => state.values[gate_index] = Some(Wire::Known(*value)),
+            _ => by_level[levels[gate_index]].push(gate_index),
+        }
+    }
+
+    // The last level reading each value; the output is never released
+    let mut last_use_level = levels.clone();
+    for (gate_index, gate) in gates.iter().enumerate() {
+        let operands = match gate {
+            Gate::And(left, right) | Gate::Or(left, right) | Gate::Xor(left, right) => vec![left.0, right.0],
+            Gate::Not(input) => vec![input.0],
+            Gate::Input | Gate::PublicInput | Gate::Constant(_) => Vec::new(),
+        };
+        for operand in operands {
+            last_use_level[operand] = last_use_level[operand].max(levels[gate_index]);
+        }
+    }
+    let output = circuit.output();
+    if let Some(level) = last_use_level.get_mut(output.0) {
+        *level = usize::MAX;
+    }
+    let mut release_at: Vec<Vec<usize>> = vec![Vec::new(); depth + 1];
+    for (node, &level) in last_use_level.iter().enumerate() {
+        if let Some(nodes) = release_at.get_mut(level) {
+            nodes.push(node);
+        }
+    }
+
+    for (level, level_gates) in by_level.iter().enumerate() {
+        let values = &state.values;
+        let results = config.map(level_gates, |&gate_index| {
+            evaluate_gate(backend, key, &gates[gate_index], values)
+        });
+        for (&gate_index, result) in level_gates.iter().zip(results) {
+            state.values[gate_index] = Some(result?);
+        }
+
+        for &node in &release_at[level] {
+            state.values[node] = None;
+        }
+    }
+
+    let output_result = state
+        .values
+        .get_mut(output.0)
+        .and_then(Option::take)
+        .ok_or_else(|| anyhow::anyhow!("Output gate {} not computed", output.0))?;
+
+    Ok(vec![output_result.into_ciphertext(backend, key)])
+}
+
+/// Evaluate a constant or logic gate from the already computed `values`.
+///
+/// Known operands are folded, so the backend is only called for gates whose
+/// result actually depends on ciphertexts. Input gates have no operands and
+/// must be fed by the caller.
+pub(crate) fn evaluate_gate<B: Backend>(
+    backend: &B,
+    key: &B::Key,
+    gate: &Gate,
+    values: &[Option<Wire<B::Ciphertext>>],
+) -> Result<Wire<B::Ciphertext>> {
+    evaluate_gate_with(backend, key, gate, |node, role| operand(values, node, role))
+}
+
+/// Evaluate a constant or logic gate, looking up operands with `lookup`.
+///
+/// `lookup` receives the operand node and its role (e.g. `"Left"`) for error
+/// messages.
+pub(crate) fn evaluate_gate_with<'v, B, F>(
+    backend: &B,
+    key: &B::Key,
+    gate: &Gate,
+    lookup: F,
+) -> Result<Wire<B::Ciphertext>>
+where
+    B: Backend,
+    B::Ciphertext: 'v,
+    F: Fn(NodeId, &str) -> Result<&'v Wire<B::Ciphertext>>,
+{
+    let result = match gate {
+        Gate::Input | Gate::PublicInput => anyhow::bail!("Input gates must be fed by the caller"),
+        Gate::Constant(value) => Wire::Known(*value),
+        Gate::And(left, right) => and(backend, key, lookup(*left, "Left")?, lookup(*right, "Right")?),
+        Gate::Or(left, right) => or(backend, key, lookup(*left, "Left")?, lookup(*right, "Right")?),
+        Gate::Xor(left, right) => xor(backend, key, lookup(*left, "Left")?, lookup(*right, "Right")?),
+        Gate::Not(input) => not(backend, key, lookup(*input, "Not")?),
+    };
+    Ok(result)
+}
+
+/// Compute the majority of three wires, folding known values.
+pub(crate) fn majority<B: Backend>(
+    backend: &B,
+    key: &B::Key,
+    wires: [&Wire<B::Ciphertext>; 3],
+) -> Wire<B::Ciphertext> {
+    match wires {
+        [Wire::Cipher(a), Wire::Cipher(b), Wire::Cipher(c)] => Wire::Cipher(backend.majority(key, a, b, c)),
+        // With one known value the majority is decided by the other two when
+        // they agree, so it reduces to a single gate
+        [Wire::Known(known), a, b] | [a, Wire::Known(known), b] | [a, b, Wire::Known(known)] => {
+            if *known {
+                or(backend, key, a, b)
+            } else {
+                and(backend, key, a, b)
+            }
+        }
+    }
+}
+
+/// Evaluate an AND gate, folding known operands.
+fn and<B: Backend>(backend: &B, key: &B::Key, left: &Wire<B::Ciphertext>, right: &Wire<B::Ciphertext>) -> Wire<B::Ciphertext> {
+    match (left, right) {
+        (Wire::Known(false), _) | (_, Wire::Known(false)) => Wire::Known(false),
+        (Wire::Known(true), other) | (other, Wire::Known(true)) => other.clone(),
+        (Wire::Cipher(l), Wire::Cipher(r)) => Wire::Cipher(backend.and(key, l, r)),
+    }
+}
+
+/// Evaluate an OR gate, folding known operands.
+fn or<B: Backend>(backend: &B, key: &B::Key, left: &Wire<B::Ciphertext>, right: &Wire<B::Ciphertext>) -> Wire<B::Ciphertext> {
+    match (left, right) {
+        (Wire::Known(true), _) | (_, Wire::Known(true)) => Wire::Known(true),
+        (Wire::Known(false), other) | (other, Wire::Known(false)) => other.clone(),
+        (Wire::Cipher(l), Wire::Cipher(r)) => Wire::Cipher(backend.or(key, l, r)),
+    }
+}
+
+/// Evaluate an XOR gate, folding known operands.
+fn xor<B: Backend>(backend: &B, key: &B::Key, left: &Wire<B::Ciphertext>, right: &Wire<B::Ciphertext>) -> Wire<B::Ciphertext> {
+    match (left, right) {
+        (Wire::Known(l), Wire::Known(r)) => Wire::Known(l ^ r),
+        (Wire::Known(false), other) | (other, Wire::Known(false)) => other.clone(),
+        (Wire::Known(true), other) | (other, Wire::Known(true)) => not(backend, key, other),
+        (Wire::Cipher(l), Wire::Cipher(r)) => Wire::Cipher(backend.xor(key, l, r)),
+    }
+}
+
+/// Evaluate a NOT gate, folding a known operand.
+fn not<B: Backend>(backend: &B, key: &B::Key, input: &Wire<B::Ciphertext>) -> Wire<B::Ciphertext> {
+    match input {
+        Wire::Known(value) => Wire::Known(!value),
+        Wire::Cipher(ct) => Wire::Cipher(backend.not(key, ct)),
+    }
+}
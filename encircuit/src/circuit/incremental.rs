@@ -0,0 +1,230 @@
+/*!
+Incremental re-evaluation of circuits.
+
+When the same circuit is evaluated repeatedly and only a few inputs change
+between runs (for example one updated field in an encrypted record), most
+gates produce the same ciphertexts as before. An [`IncrementalEvaluator`]
+keeps every intermediate value of the previous run and, given a set of
+replaced inputs, recomputes only the gates in their transitive fan-out.
+
+Caching every wire trades memory for time: the evaluator holds one value per
+gate, whereas a regular evaluation only keeps the values still needed by the
+remaining gates.
+
+## Example
+
+```rust
+use encircuit::prelude::*;
+use encircuit::{IncrementalEvaluator, MockBackend, MockCiphertext};
+# fn main() -> anyhow::Result<()> {
+let mut builder = CircuitBuilder::default();
+let a = builder.input();
+let b = builder.input();
+let c = builder.input();
+let ab = builder.and(a, b);
+let output = builder.xor(ab, c);
+let circuit = builder.finish(output);
+
+let mut evaluator = IncrementalEvaluator::new(MockBackend::new(), circuit);
+let inputs = [true, true, false].map(MockCiphertext::encrypt).to_vec();
+let result = evaluator.evaluate(&(), inputs, &[])?;
+assert!(result[0].decrypt());
+
+// Only the XOR depends on input 2, so a single gate is recomputed
+let result = evaluator.update(&(), vec![(2, MockCiphertext::encrypt(true))])?;
+assert!(!result[0].decrypt());
+assert_eq!(evaluator.last_recomputed_gates(), 1);
+# Ok(())
+# }
+```
+
+With TFHE keys, use [`TfheBackend`] and pass `server_key.tfhe_key()?` as the
+key.
+
+[`TfheBackend`]: crate::TfheBackend
+*/
+
+use crate::{
+    backend::Backend,
+    circuit::{
+        evaluator::{self, Wire},
+        Circuit, Gate,
+    },
+};
+use anyhow::Result;
+
+/// Evaluates a circuit and re-evaluates it after some inputs were replaced.
+///
+/// Call [`IncrementalEvaluator::evaluate`] once with the full set of inputs,
+/// then [`IncrementalEvaluator::update`] with the inputs that changed. The key
+/// is passed to every call, like with [`Backend`] methods, and must be the
+/// same for all of them.
+pub struct IncrementalEvaluator<B: Backend> {
+    backend: B,
+    circuit: Circuit,
+    /// Node index of every input gate, in input order.
+    input_nodes: Vec<usize>,
+    /// Cached value of every gate from the previous run.
+    values: Vec<Option<Wire<B::Ciphertext>>>,
+    /// Number of gates evaluated by the last call.
+    last_recomputed: usize,
+}
+
+impl<B: Backend> IncrementalEvaluator<B> {
+    /// Create an incremental evaluator for `circuit` on `backend`.
+    ///
+    /// Nothing is evaluated until [`IncrementalEvaluator::evaluate`] is called.
+    pub fn new(backend: B, circuit: Circuit) -> Self {
+        let input_nodes = circuit
+            .gates()
+            .iter()
+            .enumerate()
+            .filter(|(_, gate)| matches!(gate, Gate::Input))
+            .map(|(node, _)| node)
+            .collect();
+        let values = vec![None; circuit.gate_count()];
+
+        Self {
+            backend,
+            circuit,
+            input_nodes,
+            values,
+            last_recomputed: 0,
+        }
+    }
+
+    /// Evaluate the whole circuit and cache every intermediate value.
+    ///
+    /// This discards the results of any previous run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of inputs or public inputs doesn't match
+    /// the circuit, or evaluation fails.
+    pub fn evaluate(
+        &mut self,
+        key: &B::Key,
+        inputs: Vec<B::Ciphertext>,
+        public_inputs: &[bool],
+    ) -> Result<Vec<B::Ciphertext>> {
+        if inputs.len() != self.input_nodes.len() {
+            anyhow::bail!(
+                "Input count mismatch: expected {}, got {}",
+                self.input_nodes.len(),
+                inputs.len()
+            );
+        }
+        self.circuit.check_public_inputs(public_inputs)?;
+
+        let mut inputs = inputs.into_iter();
+        let mut public_inputs = public_inputs.iter();
+        self.values.iter_mut().for_each(|value| *value = None);
+
+        for (gate_index, gate) in self.circuit.gates().iter().enumerate() {
+            let value = match gate {
+                Gate::Input => Wire::Cipher(
+                    inputs
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("Missing input for gate {}", gate_index))?,
+                ),
+                Gate::PublicInput => Wire::Known(
+                    *public_inputs
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("Missing public input for gate {}", gate_index))?,
+                ),
+                _ => evaluator::evaluate_gate(&self.backend, key, gate, &self.values)?,
+            };
+            self.values[gate_index] = Some(value);
+        }
+
+        self.last_recomputed = self.circuit.gate_count();
+        self.output(key)
+    }
+
+    /// Replace some inputs and recompute the gates that depend on them.
+    ///
+    /// `replaced` holds `(input index, new ciphertext)` pairs, where the input
+    /// index is the position of the input in the order inputs were added to
+    /// the circuit (the same order as for [`Circuit::encrypt_inputs`]). Only
+    /// the transitive fan-out of the replaced inputs is recomputed; every
+    /// other gate keeps its cached value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`IncrementalEvaluator::evaluate`] has not been
+    /// called yet, an input index is out of range, or evaluation fails.
+    pub fn update(
+        &mut self,
+        key: &B::Key,
+        replaced: Vec<(usize, B::Ciphertext)>,
+    ) -> Result<Vec<B::Ciphertext>> {
+        if !self.is_evaluated() {
+            anyhow::bail!("The circuit must be evaluated before inputs can be updated");
+        }
+
+        let gates = self.circuit.gates();
+        let mut dirty = vec![false; gates.len()];
+        for (input, ciphertext) in replaced {
+            let node = *self.input_nodes.get(input).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Input index {} is out of range for a circuit with {} inputs",
+                    input,
+                    self.input_nodes.len()
+                )
+            })?;
+            self.values[node] = Some(Wire::Cipher(ciphertext));
+            dirty[node] = true;
+        }
+
+        // Gates are stored in topological order, so a single forward pass
+        // visits every gate after all of its operands.
+        let mut recomputed = 0;
+        for (gate_index, gate) in gates.iter().enumerate() {
+            let depends_on_dirty = match gate {
+                Gate::And(left, right) | Gate::Or(left, right) | Gate::Xor(left, right) => {
+                    dirty[left.0] || dirty[right.0]
+                }
+                Gate::Not(input) => dirty[input.0],
+                Gate::Input | Gate::PublicInput | Gate::Constant(_) => false,
+            };
+            if depends_on_dirty {
+                self.values[gate_index] = Some(evaluator::evaluate_gate(&self.backend, key, gate, &self.values)?);
+                dirty[gate_index] = true;
+                recomputed += 1;
+            }
+        }
+
+        self.last_recomputed = recomputed;
+        self.output(key)
+    }
+
+    /// Get the number of gates evaluated by the last call to
+    /// [`IncrementalEvaluator::evaluate`] or [`IncrementalEvaluator::update`].
+    pub fn last_recomputed_gates(&self) -> usize {
+        self.last_recomputed
+    }
+
+    /// Check whether a full evaluation has been run.
+    pub fn is_evaluated(&self) -> bool {
+        self.values.iter().all(Option::is_some)
+    }
+
+    /// Get the circuit being evaluated.
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// Get the backend used for evaluation.
+    pub fn backend(&self) -> &B {
+        &self.backend
+    }
+
+    /// Materialize the cached output value.
+    fn output(&self, key: &B::Key) -> Result<Vec<B::Ciphertext>> {
+        let output = self.circuit.output();
+        let value = self.values[output.0]
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Output gate {} not computed", output.0))?;
+        Ok(vec![value.into_ciphertext(&self.backend, key)])
+    }
+}
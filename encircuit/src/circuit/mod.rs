@@ -5,12 +5,31 @@ This module provides the core functionality for building, encrypting, and evalua
 Boolean circuits using fully homomorphic encryption.
 */
 
+pub mod assembly;
+#[cfg(feature = "async")]
+pub mod asynchronous;
 pub mod builder;
+pub mod checkpoint;
 #[allow(clippy::module_inception)]
 pub mod circuit;
+pub mod control;
+#[cfg(feature = "debug-oracle")]
+pub mod debug;
 pub mod encrypted;
+pub(crate) mod evaluator;
+pub mod incremental;
+pub mod profile;
+pub mod redundancy;
 
 // Re-exports for convenience
+pub use assembly::{InputAssembly, InputContribution, InputPort};
 pub use builder::{CircuitBuilder, Gate, NodeId};
+pub use checkpoint::EvaluationCheckpoint;
 pub use circuit::{Circuit, CircuitComplexity};
+pub use control::{CancellationToken, EvaluationProgress};
+#[cfg(feature = "debug-oracle")]
+pub use debug::{DebugReport, GateDivergence};
 pub use encrypted::EncryptedCircuit;
+pub use incremental::IncrementalEvaluator;
+pub use profile::{EvaluationProfile, GateTiming, ProfileGroup};
+pub use redundancy::{RedundancyCost, RedundancyPlan, RedundantCircuit};
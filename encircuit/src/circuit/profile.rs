@@ -0,0 +1,317 @@
+/*!
+Per-gate evaluation tracing and profiling.
+
+[`CircuitComplexity::estimated_evaluation_time_ms`] is a static estimate. To
+see where time actually goes, evaluate with
+[`EncryptedCircuit::try_evaluate_profiled`], which records the wall time of
+every gate and returns an [`EvaluationProfile`].
+
+A profile can be summarized by gate type, by label (see
+[`CircuitBuilder::label`]) or by circuit level, printed as a table, or
+exported in the Chrome trace event format for viewing in `chrome://tracing`
+or [Perfetto](https://ui.perfetto.dev).
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, server_key) = Keyset::generate(&params)?.split();
+let mut builder = CircuitBuilder::default();
+let x = builder.input();
+let y = builder.input();
+let sum = builder.xor(x, y);
+let carry = builder.and(x, y);
+builder.label(sum, "half_adder");
+builder.label(carry, "half_adder");
+let output = builder.or(sum, carry);
+let circuit = builder.finish(output);
+
+let encrypted = circuit.encrypt_inputs(&[true, false], &client_key)?;
+let (result, profile) = encrypted.try_evaluate_profiled(&server_key)?;
+
+println!("{}", profile);
+std::fs::write("trace.json", profile.to_chrome_trace_json())?;
+# Ok(())
+# }
+```
+
+[`CircuitComplexity::estimated_evaluation_time_ms`]: crate::CircuitComplexity::estimated_evaluation_time_ms
+[`EncryptedCircuit::try_evaluate_profiled`]: crate::EncryptedCircuit::try_evaluate_profiled
+[`CircuitBuilder::label`]: crate::CircuitBuilder::label
+*/
+
+use crate::circuit::{Circuit, Gate, NodeId};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Label used for gates that were not labelled when building the circuit.
+const UNLABELED: &str = "<unlabeled>";
+
+/// Timing information for a single evaluated gate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateTiming {
+    /// The node that was evaluated.
+    pub node: NodeId,
+    /// The gate type name (e.g. `"AND"`).
+    pub gate_type: &'static str,
+    /// The level of the gate in the circuit (see [`Circuit::levels`]).
+    pub level: usize,
+    /// The label attached to the node, if any.
+    pub label: Option<String>,
+    /// Offset of the gate's start from the start of the evaluation.
+    pub start: Duration,
+    /// Wall time spent evaluating the gate.
+    pub duration: Duration,
+}
+
+/// Aggregated timings for a group of gates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileGroup {
+    /// The group key (gate type, label or level).
+    pub key: String,
+    /// Number of gates in the group.
+    pub count: usize,
+    /// Total wall time spent in the group's gates.
+    pub total: Duration,
+    /// Longest single gate in the group.
+    pub max: Duration,
+}
+
+impl ProfileGroup {
+    /// Get the mean wall time per gate in this group.
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.count as u32
+    }
+}
+
+/// Wall-time profile of a homomorphic circuit evaluation.
+///
+/// Produced by [`EncryptedCircuit::try_evaluate_profiled`]. The `Display`
+/// implementation renders the same tables as [`EvaluationProfile::to_table`].
+///
+/// [`EncryptedCircuit::try_evaluate_profiled`]: crate::EncryptedCircuit::try_evaluate_profiled
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationProfile {
+    timings: Vec<GateTiming>,
+    total: Duration,
+}
+
+impl EvaluationProfile {
+    /// Get the timing of every evaluated gate, in evaluation order.
+    pub fn gate_timings(&self) -> &[GateTiming] {
+        &self.timings
+    }
+
+    /// Get the wall time of the whole evaluation.
+    pub fn total_time(&self) -> Duration {
+        self.total
+    }
+
+    /// Get the wall time spent inside gates.
+    ///
+    /// The difference to [`EvaluationProfile::total_time`] is evaluator overhead.
+    pub fn gate_time(&self) -> Duration {
+        self.timings.iter().map(|timing| timing.duration).sum()
+    }
+
+    /// Summarize the profile by gate type, slowest group first.
+    pub fn by_gate_type(&self) -> Vec<ProfileGroup> {
+        let mut groups = Self::group(&self.timings, |timing| timing.gate_type.to_string());
+        groups.sort_by_key(|group| std::cmp::Reverse(group.total));
+        groups
+    }
+
+    /// Summarize the profile by node label, slowest group first.
+    ///
+    /// Gates without a label are grouped under `"<unlabeled>"`.
+    pub fn by_label(&self) -> Vec<ProfileGroup> {
+        let mut groups = Self::group(&self.timings, |timing| {
+            timing.label.clone().unwrap_or_else(|| UNLABELED.to_string())
+        });
+        groups.sort_by_key(|group| std::cmp::Reverse(group.total));
+        groups
+    }
+
+    /// Summarize the profile by circuit level, in increasing level order.
+    pub fn by_level(&self) -> Vec<ProfileGroup> {
+        let mut levels: BTreeMap<usize, Vec<&GateTiming>> = BTreeMap::new();
+        for timing in &self.timings {
+            levels.entry(timing.level).or_default().push(timing);
+        }
+
+        levels
+            .into_iter()
+            .map(|(level, timings)| Self::summarize(level.to_string(), timings))
+            .collect()
+    }
+
+    /// Render the profile as plain-text tables grouped by gate type, label and level.
+    pub fn to_table(&self) -> String {
+        let mut table = String::new();
+        Self::write_table(&mut table, "Gate type", &self.by_gate_type());
+        table.push('\n');
+        Self::write_table(&mut table, "Label", &self.by_label());
+        table.push('\n');
+        Self::write_table(&mut table, "Level", &self.by_level());
+        table.push_str(&format!(
+            "\nTotal: {:.3} ms ({:.3} ms in gates)\n",
+            self.total.as_secs_f64() * 1e3,
+            self.gate_time().as_secs_f64() * 1e3
+        ));
+        table
+    }
+
+    /// Export the profile in the Chrome trace event JSON format.
+    ///
+    /// Every gate becomes a complete (`"ph": "X"`) event whose category is its
+    /// label, with the node index and level in `args`. The output can be
+    /// loaded in `chrome://tracing` or Perfetto.
+    pub fn to_chrome_trace_json(&self) -> String {
+        let events: Vec<String> = self
+            .timings
+            .iter()
+            .map(|timing| {
+                format!(
+                    "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":1,\"args\":{{\"node\":{},\"level\":{}}}}}",
+                    timing.gate_type,
+                    json_escape(timing.label.as_deref().unwrap_or(UNLABELED)),
+                    timing.start.as_secs_f64() * 1e6,
+                    timing.duration.as_secs_f64() * 1e6,
+                    timing.node.id(),
+                    timing.level
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"traceEvents\":[{}],\"displayTimeUnit\":\"ms\"}}",
+            events.join(",")
+        )
+    }
+
+    /// Group timings by the key returned from `key_of`.
+    fn group<F>(timings: &[GateTiming], key_of: F) -> Vec<ProfileGroup>
+    where
+        F: Fn(&GateTiming) -> String,
+    {
+        let mut groups: BTreeMap<String, Vec<&GateTiming>> = BTreeMap::new();
+        for timing in timings {
+            groups.entry(key_of(timing)).or_default().push(timing);
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, timings)| Self::summarize(key, timings))
+            .collect()
+    }
+
+    /// Aggregate a set of timings into a group.
+    fn summarize(key: String, timings: Vec<&GateTiming>) -> ProfileGroup {
+        ProfileGroup {
+            key,
+            count: timings.len(),
+            total: timings.iter().map(|timing| timing.duration).sum(),
+            max: timings.iter().map(|timing| timing.duration).max().unwrap_or_default(),
+        }
+    }
+
+    /// Append one table to `out`.
+    fn write_table(out: &mut String, title: &str, groups: &[ProfileGroup]) {
+        let width = groups
+            .iter()
+            .map(|group| group.key.len())
+            .chain(std::iter::once(title.len()))
+            .max()
+            .unwrap_or(0);
+
+        out.push_str(&format!(
+            "{:<width$}  {:>8}  {:>12}  {:>12}  {:>12}\n",
+            title, "Count", "Total (ms)", "Mean (ms)", "Max (ms)"
+        ));
+        for group in groups {
+            out.push_str(&format!(
+                "{:<width$}  {:>8}  {:>12.3}  {:>12.3}  {:>12.3}\n",
+                group.key,
+                group.count,
+                group.total.as_secs_f64() * 1e3,
+                group.mean().as_secs_f64() * 1e3,
+                group.max.as_secs_f64() * 1e3
+            ));
+        }
+    }
+}
+
+impl fmt::Display for EvaluationProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_table())
+    }
+}
+
+/// Records gate timings while a circuit is being evaluated.
+pub(crate) struct Profiler {
+    started: Instant,
+    levels: Vec<usize>,
+    timings: Vec<GateTiming>,
+}
+
+impl Profiler {
+    /// Start profiling an evaluation of `circuit`.
+    pub(crate) fn new(circuit: &Circuit) -> Self {
+        Self {
+            started: Instant::now(),
+            levels: circuit.levels(),
+            timings: Vec::with_capacity(circuit.gate_count()),
+        }
+    }
+
+    /// Record that the gate at `gate_index` ran from `start` for `duration`.
+    pub(crate) fn record(
+        &mut self,
+        circuit: &Circuit,
+        gate: &Gate,
+        gate_index: usize,
+        start: Instant,
+        duration: Duration,
+    ) {
+        let node = NodeId(gate_index);
+        self.timings.push(GateTiming {
+            node,
+            gate_type: gate.name(),
+            level: self.levels[gate_index],
+            label: circuit.label(node).map(str::to_string),
+            start: start.saturating_duration_since(self.started),
+            duration,
+        });
+    }
+
+    /// Stop profiling and build the final profile.
+    pub(crate) fn finish(self) -> EvaluationProfile {
+        EvaluationProfile {
+            total: self.started.elapsed(),
+            timings: self.timings,
+        }
+    }
+}
+
+/// Escape a string for inclusion in a JSON string literal.
+fn json_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,528 @@
+/*!
+Redundant evaluation to tolerate bootstrapping failures.
+
+Every bootstrap under `Scenario::FastDemo` or `Scenario::SafeAndBalanced`
+fails with a small but non-negligible probability, and over millions of
+gates those failures add up. Redundant evaluation lowers the effective
+failure probability without moving to a slower parameter set: selected
+sub-circuits (or the whole circuit) are evaluated three times and their
+results are merged through a homomorphic majority gate, so a single failed
+replica no longer corrupts the result.
+
+TFHE bootstrapping is deterministic, so replicas computed from the same
+ciphertexts would fail in exactly the same way. Replicas are therefore made
+independent at the source: a protected sub-circuit always extends back to the
+circuit inputs it depends on (the *cone* of its root), and the client
+encrypts each of those inputs three times with
+[`Circuit::encrypt_inputs_redundant`]. Replicas are merged wherever a value
+leaves the protected region, i.e. when an unprotected gate consumes it or it
+is the circuit output.
+
+The extra cost is reported by [`RedundancyPlan::cost`].
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+use encircuit::RedundancyPlan;
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, server_key) = Keyset::generate(&params)?.split();
+let mut builder = CircuitBuilder::default();
+let x = builder.input();
+let y = builder.input();
+let z = builder.input();
+let critical = builder.and(x, y);
+let output = builder.xor(critical, z);
+let circuit = builder.finish(output);
+
+// Protect only the cone of the critical AND gate
+let plan = RedundancyPlan::cones(&circuit, &[critical])?;
+println!("{} extra bootstraps", plan.cost().extra_bootstraps());
+
+let redundant = circuit.encrypt_inputs_redundant(&[true, true, false], &client_key, &plan)?;
+let result = redundant.try_evaluate(&server_key)?;
+# Ok(())
+# }
+```
+
+[`Circuit::encrypt_inputs_redundant`]: crate::Circuit::encrypt_inputs_redundant
+*/
+
+use crate::{
+    backend::{Backend, TfheBackend},
+    ciphertext::{BoolCt, Encryptable},
+    circuit::{
+        evaluator::{self, Wire},
+        Circuit, Gate, NodeId,
+    },
+    keys::{ClientKeyBytes, ServerKeyBytes},
+};
+use anyhow::Result;
+
+/// Number of bootstraps of a majority gate on the [`TfheBackend`].
+const MAJORITY_BOOTSTRAPS: usize = 3;
+
+/// The gates of a circuit that are evaluated redundantly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedundancyPlan {
+    protected: Vec<bool>,
+    cost: RedundancyCost,
+}
+
+/// The extra work caused by a [`RedundancyPlan`].
+///
+/// Bootstrap counts follow the static cost model of
+/// [`Circuit::complexity_estimate`]: every AND, OR and XOR gate costs one
+/// bootstrap, NOT gates are free, and a majority gate costs three bootstraps.
+/// Gates folded away at evaluation time (see [`CircuitBuilder::public_input`])
+/// are counted as well.
+///
+/// [`CircuitBuilder::public_input`]: crate::CircuitBuilder::public_input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RedundancyCost {
+    /// Bootstraps of a regular evaluation.
+    pub base_bootstraps: usize,
+    /// Number of logic gates evaluated three times.
+    pub protected_gates: usize,
+    /// Extra binary gates evaluated for the two additional replicas.
+    pub replicated_binary_gates: usize,
+    /// Extra NOT gates evaluated for the two additional replicas.
+    pub replicated_not_gates: usize,
+    /// Majority gates merging replicas.
+    pub majority_gates: usize,
+    /// Extra input encryptions for the two additional replicas.
+    pub extra_input_encryptions: usize,
+}
+
+impl RedundancyCost {
+    /// Get the number of bootstraps added by redundant evaluation.
+    pub fn extra_bootstraps(&self) -> usize {
+        self.replicated_binary_gates + self.majority_gates * MAJORITY_BOOTSTRAPS
+    }
+
+    /// Get the total number of bootstraps of the redundant evaluation.
+    pub fn total_bootstraps(&self) -> usize {
+        self.base_bootstraps + self.extra_bootstraps()
+    }
+
+    /// Get the ratio of redundant to regular bootstraps (1.0 means no overhead).
+    pub fn overhead_ratio(&self) -> f64 {
+        if self.base_bootstraps == 0 {
+            return 1.0;
+        }
+        self.total_bootstraps() as f64 / self.base_bootstraps as f64
+    }
+}
+
+impl RedundancyPlan {
+    /// Protect the whole circuit.
+    ///
+    /// Every gate the output depends on is evaluated three times, and a
+    /// single majority gate merges the replicas of the output.
+    pub fn whole(circuit: &Circuit) -> Self {
+        Self::for_roots(circuit, &[circuit.output().id()])
+    }
+
+    /// Protect the cones of the given nodes.
+    ///
+    /// A cone consists of a node and every gate it transitively depends on,
+    /// down to the circuit inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a node does not belong to the circuit.
+    pub fn cones(circuit: &Circuit, roots: &[NodeId]) -> Result<Self> {
+        let roots: Vec<usize> = roots.iter().map(|root| root.id()).collect();
+        if let Some(root) = roots.iter().find(|&&root| root >= circuit.gate_count()) {
+            anyhow::bail!(
+                "Node {} is out of bounds for a circuit with {} gates",
+                root,
+                circuit.gate_count()
+            );
+        }
+        Ok(Self::for_roots(circuit, &roots))
+    }
+
+    /// Check whether the gate at `node` is evaluated redundantly.
+    pub fn is_protected(&self, node: NodeId) -> bool {
+        self.protected.get(node.id()).copied().unwrap_or(false)
+    }
+
+    /// Get the extra cost of the redundant evaluation.
+    pub fn cost(&self) -> &RedundancyCost {
+        &self.cost
+    }
+
+    /// Mark the cones of `roots` and compute the cost.
+    fn for_roots(circuit: &Circuit, roots: &[usize]) -> Self {
+        let gates = circuit.gates();
+        let mut protected = vec![false; gates.len()];
+        for &root in roots {
+            protected[root] = true;
+        }
+
+        // Gates only reference earlier gates, so a backward pass marks whole cones
+        for (node, gate) in gates.iter().enumerate().rev() {
+            if !protected[node] {
+                continue;
+            }
+            match gate {
+                Gate::And(left, right) | Gate::Or(left, right) | Gate::Xor(left, right) => {
+                    protected[left.0] = true;
+                    protected[right.0] = true;
+                }
+                Gate::Not(input) => protected[input.0] = true,
+                Gate::Input | Gate::PublicInput | Gate::Constant(_) => {}
+            }
+        }
+
+        let cost = Self::compute_cost(circuit, &protected);
+        Self { protected, cost }
+    }
+
+    /// Count the extra gates caused by protecting the marked gates.
+    fn compute_cost(circuit: &Circuit, protected: &[bool]) -> RedundancyCost {
+        let gates = circuit.gates();
+        let mut cost = RedundancyCost::default();
+        let mut merged = vec![false; gates.len()];
+
+        // Protected ciphertext values leaving the protected region are merged
+        // once; constants and public inputs are the same in every replica
+        let mut merge = |node: usize, cost: &mut RedundancyCost| {
+            let needs_majority = protected[node]
+                && !merged[node]
+                && !matches!(gates[node], Gate::Constant(_) | Gate::PublicInput);
+            if needs_majority {
+                merged[node] = true;
+                cost.majority_gates += 1;
+            }
+        };
+
+        for (node, gate) in gates.iter().enumerate() {
+            match gate {
+                Gate::And(left, right) | Gate::Or(left, right) | Gate::Xor(left, right) => {
+                    cost.base_bootstraps += 1;
+                    if protected[node] {
+                        cost.protected_gates += 1;
+                        cost.replicated_binary_gates += 2;
+                    } else {
+                        merge(left.0, &mut cost);
+                        merge(right.0, &mut cost);
+                    }
+                }
+                Gate::Not(input) => {
+                    if protected[node] {
+                        cost.protected_gates += 1;
+                        cost.replicated_not_gates += 2;
+                    } else {
+                        merge(input.0, &mut cost);
+                    }
+                }
+                Gate::Input => {
+                    if protected[node] {
+                        cost.extra_input_encryptions += 2;
+                    }
+                }
+                Gate::PublicInput | Gate::Constant(_) => {}
+            }
+        }
+        merge(circuit.output().id(), &mut cost);
+
+        cost
+    }
+
+    /// Check that the plan was built for `circuit`.
+    fn check_circuit(&self, circuit: &Circuit) -> Result<()> {
+        if self.protected.len() != circuit.gate_count() {
+            anyhow::bail!(
+                "Redundancy plan covers {} gates, but the circuit has {}",
+                self.protected.len(),
+                circuit.gate_count()
+            );
+        }
+        Ok(())
+    }
+
+    /// Get the number of replicas expected for each input, in input order.
+    fn replicas_per_input(&self, circuit: &Circuit) -> Vec<usize> {
+        circuit
+            .gates()
+            .iter()
+            .enumerate()
+            .filter(|(_, gate)| matches!(gate, Gate::Input))
+            .map(|(node, _)| if self.protected[node] { 3 } else { 1 })
+            .collect()
+    }
+}
+
+/// The value of a wire during redundant evaluation.
+#[derive(Clone)]
+enum Replicated<C> {
+    /// A single value, shared by all replicas.
+    Single(Wire<C>),
+    /// One value per replica.
+    Triple([Wire<C>; 3]),
+}
+
+impl<C> Replicated<C> {
+    /// Get the value seen by the given replica.
+    fn replica(&self, replica: usize) -> &Wire<C> {
+        match self {
+            Replicated::Single(wire) => wire,
+            Replicated::Triple(wires) => &wires[replica],
+        }
+    }
+}
+
+impl Circuit {
+    /// Encrypt the circuit inputs for a redundant evaluation.
+    ///
+    /// Inputs protected by `plan` are encrypted three times, so that the
+    /// replicas computed from them fail independently.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plan was built for a different circuit, the
+    /// number of inputs doesn't match, or encryption fails.
+    pub fn encrypt_inputs_redundant(
+        &self,
+        inputs: &[bool],
+        client_key: &ClientKeyBytes,
+        plan: &RedundancyPlan,
+    ) -> Result<RedundantCircuit> {
+        plan.check_circuit(self)?;
+        let replicas = plan.replicas_per_input(self);
+        if inputs.len() != replicas.len() {
+            anyhow::bail!(
+                "Input count mismatch: expected {}, got {}",
+                replicas.len(),
+                inputs.len()
+            );
+        }
+
+        let encrypted_inputs = inputs
+            .iter()
+            .zip(replicas)
+            .map(|(input, count)| (0..count).map(|_| input.encrypt(client_key)).collect())
+            .collect::<Result<Vec<Vec<BoolCt>>>>()?;
+
+        Ok(RedundantCircuit {
+            circuit: self.clone(),
+            plan: plan.clone(),
+            encrypted_inputs,
+            public_inputs: Vec::new(),
+        })
+    }
+
+    /// Evaluate the circuit redundantly on an arbitrary [`Backend`].
+    ///
+    /// `inputs` holds the replicas of every input, in input order: three
+    /// independently encrypted values for inputs protected by `plan`, and a
+    /// single value for the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plan was built for a different circuit, the
+    /// number of inputs, replicas or public inputs doesn't match, or
+    /// evaluation fails.
+    pub fn evaluate_redundant_with<B: Backend>(
+        &self,
+        backend: &B,
+        key: &B::Key,
+        plan: &RedundancyPlan,
+        inputs: &[Vec<B::Ciphertext>],
+        public_inputs: &[bool],
+    ) -> Result<Vec<B::Ciphertext>> {
+        plan.check_circuit(self)?;
+        self.check_public_inputs(public_inputs)?;
+
+        let replicas = plan.replicas_per_input(self);
+        if inputs.len() != replicas.len() {
+            anyhow::bail!(
+                "Input count mismatch: expected {}, got {}",
+                replicas.len(),
+                inputs.len()
+            );
+        }
+        for (index, (input, expected)) in inputs.iter().zip(&replicas).enumerate() {
+            if input.len() != *expected {
+                anyhow::bail!(
+                    "Input {} has {} replicas, expected {}",
+                    index,
+                    input.len(),
+                    expected
+                );
+            }
+        }
+
+        let gates = self.gates();
+        let last_uses = self.last_uses();
+        let mut values: Vec<Option<Replicated<B::Ciphertext>>> = vec![None; gates.len()];
+        let mut merged: Vec<Option<Wire<B::Ciphertext>>> = vec![None; gates.len()];
+        let mut inputs = inputs.iter();
+        let mut public_inputs = public_inputs.iter();
+
+        for (gate_index, gate) in gates.iter().enumerate() {
+            let operands = match gate {
+                Gate::And(left, right) | Gate::Or(left, right) | Gate::Xor(left, right) => vec![left.0, right.0],
+                Gate::Not(input) => vec![input.0],
+                Gate::Input | Gate::PublicInput | Gate::Constant(_) => Vec::new(),
+            };
+
+            let value = match gate {
+                Gate::Input => {
+                    let input = inputs
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("Missing input for gate {}", gate_index))?;
+                    match input.as_slice() {
+                        [single] => Replicated::Single(Wire::Cipher(single.clone())),
+                        [a, b, c] => Replicated::Triple([
+                            Wire::Cipher(a.clone()),
+                            Wire::Cipher(b.clone()),
+                            Wire::Cipher(c.clone()),
+                        ]),
+                        _ => anyhow::bail!("Invalid replica count for gate {}", gate_index),
+                    }
+                }
+                Gate::PublicInput => {
+                    let input = public_inputs
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("Missing public input for gate {}", gate_index))?;
+                    Replicated::Single(Wire::Known(*input))
+                }
+                _ if plan.protected[gate_index] => {
+                    let evaluate_replica = |replica: usize| {
+                        evaluator::evaluate_gate_with(backend, key, gate, |node, role| {
+                            values[node.0]
+                                .as_ref()
+                                .map(|value| value.replica(replica))
+                                .ok_or_else(|| anyhow::anyhow!("{} input gate {} not yet computed", role, node.0))
+                        })
+                    };
+                    Replicated::Triple([evaluate_replica(0)?, evaluate_replica(1)?, evaluate_replica(2)?])
+                }
+                _ => {
+                    // Merge protected operands leaving the protected region
+                    for &operand in &operands {
+                        merge(backend, key, &values, &mut merged, operand)?;
+                    }
+                    let result = evaluator::evaluate_gate_with(backend, key, gate, |node, role| {
+                        merged[node.0]
+                            .as_ref()
+                            .or_else(|| values[node.0].as_ref().map(|value| value.replica(0)))
+                            .ok_or_else(|| anyhow::anyhow!("{} input gate {} not yet computed", role, node.0))
+                    })?;
+                    Replicated::Single(result)
+                }
+            };
+            values[gate_index] = Some(value);
+
+            // Release values whose last consumer was this gate
+            for operand in operands.into_iter().chain(std::iter::once(gate_index)) {
+                if last_uses[operand] == gate_index {
+                    values[operand] = None;
+                    merged[operand] = None;
+                }
+            }
+        }
+
+        let output = self.output().id();
+        merge(backend, key, &values, &mut merged, output)?;
+        let result = merged[output]
+            .take()
+            .or_else(|| match values[output].take() {
+                Some(Replicated::Single(wire)) => Some(wire),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow::anyhow!("Output gate {} not computed", output))?;
+
+        Ok(vec![result.into_ciphertext(backend, key)])
+    }
+}
+
+/// Merge the replicas of `node` through a majority gate, once.
+fn merge<B: Backend>(
+    backend: &B,
+    key: &B::Key,
+    values: &[Option<Replicated<B::Ciphertext>>],
+    merged: &mut [Option<Wire<B::Ciphertext>>],
+    node: usize,
+) -> Result<()> {
+    if merged[node].is_some() {
+        return Ok(());
+    }
+    match &values[node] {
+        Some(Replicated::Triple([a, b, c])) => {
+            merged[node] = Some(evaluator::majority(backend, key, [a, b, c]));
+            Ok(())
+        }
+        Some(Replicated::Single(_)) => Ok(()),
+        None => anyhow::bail!("Gate {} not yet computed", node),
+    }
+}
+
+/// A circuit with redundantly encrypted inputs, ready for redundant evaluation.
+///
+/// Created by [`Circuit::encrypt_inputs_redundant`].
+#[derive(Debug, Clone)]
+pub struct RedundantCircuit {
+    circuit: Circuit,
+    plan: RedundancyPlan,
+    encrypted_inputs: Vec<Vec<BoolCt>>,
+    public_inputs: Vec<bool>,
+}
+
+impl RedundantCircuit {
+    /// Supply the values of the circuit's public inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of values doesn't match the number of
+    /// public inputs in the circuit.
+    pub fn with_public_inputs(mut self, public_inputs: &[bool]) -> Result<Self> {
+        self.circuit.check_public_inputs(public_inputs)?;
+        self.public_inputs = public_inputs.to_vec();
+        Ok(self)
+    }
+
+    /// Evaluate the circuit homomorphically, merging replicas by majority.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server key is invalid or circuit evaluation fails.
+    pub fn try_evaluate(&self, server_key: &ServerKeyBytes) -> Result<Vec<BoolCt>> {
+        let tfhe_server_key = server_key.tfhe_key()
+            .map_err(|e| anyhow::anyhow!("Invalid server key: {}", e))?;
+        for (index, copies) in self.encrypted_inputs.iter().enumerate() {
+            for ciphertext in copies {
+                server_key.check_ciphertext(ciphertext, || format!("Ciphertext for input {}", index))?;
+            }
+        }
+
+        self.circuit
+            .evaluate_redundant_with(
+                &TfheBackend,
+                tfhe_server_key,
+                &self.plan,
+                &self.encrypted_inputs,
+                &self.public_inputs,
+            )
+            .map_err(|e| anyhow::anyhow!("Circuit evaluation failed: {}", e))
+    }
+
+    /// Get the underlying circuit.
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// Get the redundancy plan.
+    pub fn plan(&self) -> &RedundancyPlan {
+        &self.plan
+    }
+
+    /// Get the extra cost of the redundant evaluation.
+    pub fn cost(&self) -> &RedundancyCost {
+        self.plan.cost()
+    }
+}
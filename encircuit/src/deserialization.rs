@@ -0,0 +1,175 @@
+/*!
+Bounded deserialization of untrusted ciphertexts and keys.
+
+`from_bytes` trusts its input: it accepts buffers of any size and doesn't
+check that the decoded object fits the parameters the application uses. A
+server receiving ciphertexts or keys from clients should use the
+`safe_from_bytes` variants instead, which take a [`DeserializationConfig`]:
+
+- A **size limit** rejects oversized buffers up front, and stops the decoder
+  before it follows a forged length prefix into a huge allocation.
+- **Expected parameters** reject objects whose dimensions don't match the
+  given [`Params`], such as a ciphertext of another parameter set, which
+  would otherwise produce garbage or panic deep inside TFHE.
+
+The conformance checks are also available on their own, as
+`check_conformance` on [`BoolCt`](crate::BoolCt),
+[`ClientKeyBytes`](crate::ClientKeyBytes) and
+[`ServerKeyBytes`](crate::ServerKeyBytes).
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+use encircuit::DeserializationConfig;
+# fn main() -> anyhow::Result<()> {
+# let request: Vec<u8> = Vec::new();
+let params = Params::for_scenario(Scenario::SafeAndBalanced)?;
+let config = DeserializationConfig::new(64 * 1024).with_expected_params(&params);
+
+let ciphertext = BoolCt::safe_from_bytes(&request, &config)?;
+# Ok(())
+# }
+```
+*/
+
+use crate::params::Params;
+use anyhow::Result;
+use tfhe::boolean::parameters::{BooleanParameters, EncryptionKeyChoice};
+
+/// Limits applied when deserializing untrusted data.
+#[derive(Debug, Clone)]
+pub struct DeserializationConfig {
+    size_limit: u64,
+    expected_params: Option<Params>,
+}
+
+impl DeserializationConfig {
+    /// Accept buffers of at most `size_limit` bytes.
+    ///
+    /// Ciphertexts take a few kilobytes and server keys tens of megabytes,
+    /// depending on the parameters.
+    pub fn new(size_limit: u64) -> Self {
+        Self {
+            size_limit,
+            expected_params: None,
+        }
+    }
+
+    /// Also check that deserialized objects conform to `params`.
+    pub fn with_expected_params(mut self, params: &Params) -> Self {
+        self.expected_params = Some(params.clone());
+        self
+    }
+
+    /// Get the size limit in bytes.
+    pub fn size_limit(&self) -> u64 {
+        self.size_limit
+    }
+
+    /// Get the parameters deserialized objects are checked against, if any.
+    pub fn expected_params(&self) -> Option<&Params> {
+        self.expected_params.as_ref()
+    }
+
+    /// Decode `data` with the size limit applied.
+    ///
+    /// `what` names the object in error messages, e.g. `"ciphertext"`.
+    #[cfg(feature = "serde")]
+    pub(crate) fn deserialize<T: serde::de::DeserializeOwned>(&self, data: &[u8], what: &str) -> Result<T> {
+        use bincode::Options;
+
+        if data.len() as u64 > self.size_limit {
+            anyhow::bail!(
+                "Serialized {} is {} bytes, exceeding the limit of {} bytes",
+                what,
+                data.len(),
+                self.size_limit
+            );
+        }
+
+        // Same encoding as `bincode::deserialize`, but bounded and strict
+        bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .with_limit(self.size_limit)
+            .deserialize(data)
+            .map_err(|e| anyhow::anyhow!("Failed to deserialize {}: {}", what, e))
+    }
+}
+
+/// Size of the LWE ciphertexts produced under `params`, mask plus body.
+fn ciphertext_lwe_size(params: &BooleanParameters) -> usize {
+    match params.encryption_key_choice {
+        EncryptionKeyChoice::Big => params.glwe_dimension.0 * params.polynomial_size.0 + 1,
+        EncryptionKeyChoice::Small => params.lwe_dimension.0 + 1,
+    }
+}
+
+/// Check that a TFHE ciphertext was produced under `params`.
+pub(crate) fn check_ciphertext(ciphertext: &tfhe::boolean::ciphertext::Ciphertext, params: &Params) -> Result<()> {
+    use tfhe::boolean::ciphertext::Ciphertext;
+
+    let expected = ciphertext_lwe_size(params.tfhe_boolean_params());
+    match ciphertext {
+        Ciphertext::Encrypted(ct) if ct.lwe_size().0 != expected => anyhow::bail!(
+            "Ciphertext has LWE size {}, but the parameters produce size {}",
+            ct.lwe_size().0,
+            expected
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Check that a TFHE client key was generated for `params`.
+pub(crate) fn check_client_key(client_key: &tfhe::boolean::prelude::ClientKey, params: &Params) -> Result<()> {
+    let tfhe_params = params.tfhe_boolean_params();
+    let (secret, noise) = client_key.encryption_key_and_noise();
+    let expected_noise = match tfhe_params.encryption_key_choice {
+        EncryptionKeyChoice::Big => tfhe_params.glwe_noise_distribution,
+        EncryptionKeyChoice::Small => tfhe_params.lwe_noise_distribution,
+    };
+
+    let expected_dimension = ciphertext_lwe_size(tfhe_params) - 1;
+    if secret.as_ref().len() != expected_dimension {
+        anyhow::bail!(
+            "Client key has encryption key dimension {}, but the parameters use {}",
+            secret.as_ref().len(),
+            expected_dimension
+        );
+    }
+    if noise != expected_noise {
+        anyhow::bail!("Client key encryption noise {:?} doesn't match the parameters' {:?}", noise, expected_noise);
+    }
+    Ok(())
+}
+
+/// Check that a TFHE server key was generated for `params`.
+pub(crate) fn check_server_key(server_key: &tfhe::boolean::prelude::ServerKey, params: &Params) -> Result<()> {
+    let tfhe_params = params.tfhe_boolean_params();
+    let glwe_size = tfhe_params.glwe_dimension.0 + 1;
+    let big_dimension = tfhe_params.glwe_dimension.0 * tfhe_params.polynomial_size.0;
+
+    // One Fourier GGSW per small key coefficient, and one LWE per decomposed big key coefficient
+    let bootstrapping_key = tfhe_params.lwe_dimension.0
+        * tfhe_params.pbs_level.0
+        * glwe_size
+        * glwe_size
+        * (tfhe_params.polynomial_size.0 / 2);
+    let key_switching_key = big_dimension * tfhe_params.ks_level.0 * (tfhe_params.lwe_dimension.0 + 1);
+
+    if server_key.bootstrapping_key_size_elements() != bootstrapping_key {
+        anyhow::bail!(
+            "Server key has a bootstrapping key of {} elements, but the parameters produce {}",
+            server_key.bootstrapping_key_size_elements(),
+            bootstrapping_key
+        );
+    }
+    if server_key.key_switching_key_size_elements() != key_switching_key {
+        anyhow::bail!(
+            "Server key has a key switching key of {} elements, but the parameters produce {}",
+            server_key.key_switching_key_size_elements(),
+            key_switching_key
+        );
+    }
+    Ok(())
+}
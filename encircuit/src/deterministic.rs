@@ -0,0 +1,68 @@
+/*!
+Deterministic key generation and encryption for reproducible tests.
+
+FHE keys and ciphertexts are randomized, so golden files and regression
+tests cannot pin them. With the `insecure-deterministic-rng` feature,
+[`Keyset::generate_from_seed`] and [`Circuit::encrypt_inputs_from_seed`]
+derive all their randomness from a 32-byte seed and produce byte-identical
+output for the same seed and parameters.
+
+**Never use this outside of tests.** Anyone who knows or guesses the seed can
+regenerate the client key and decrypt everything. The feature is off by
+default and deliberately named so that enabling it stands out in review.
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+# fn main() -> anyhow::Result<()> {
+# let mut builder = CircuitBuilder::default();
+# let x = builder.input();
+# let y = builder.input();
+# let out = builder.and(x, y);
+# let circuit = builder.finish(out);
+let params = Params::for_scenario(Scenario::FastDemo)?;
+let keyset = Keyset::generate_from_seed(&params, [7; 32])?;
+
+let encrypted = circuit.encrypt_inputs_from_seed(&[true, false], keyset.client_key(), [8; 32])?;
+// Same seeds, same parameters: byte-identical keys and ciphertexts on every run
+# Ok(())
+# }
+```
+
+[`Keyset::generate_from_seed`]: crate::Keyset::generate_from_seed
+[`Circuit::encrypt_inputs_from_seed`]: crate::Circuit::encrypt_inputs_from_seed
+*/
+
+use sha2::{Digest, Sha256};
+use tfhe::boolean::engine::BooleanEngine;
+use tfhe::core_crypto::commons::generators::DeterministicSeeder;
+use tfhe::core_crypto::commons::math::random::{DefaultRandomGenerator, Seed};
+
+/// Run `f` with TFHE's thread-local Boolean engine seeded from `seed`.
+///
+/// Only randomness drawn on the calling thread is seeded, so `f` must not
+/// encrypt on other threads. A freshly seeded random engine is restored
+/// afterwards, also when `f` panics.
+pub(crate) fn with_seeded_engine<R>(seed: [u8; 32], f: impl FnOnce() -> R) -> R {
+    // TFHE seeds are 128 bits; hash the 256-bit seed down with domain separation
+    let digest = Sha256::new()
+        .chain_update(b"encircuit/seed/v1")
+        .chain_update(seed)
+        .finalize();
+    let seed = u128::from_le_bytes(digest[..16].try_into().expect("digest is 32 bytes"));
+
+    let mut seeder = DeterministicSeeder::<DefaultRandomGenerator>::new(Seed(seed));
+    BooleanEngine::replace_thread_local(BooleanEngine::new_from_seeder(&mut seeder));
+    let _restore = RestoreRandomEngine;
+    f()
+}
+
+/// Puts a randomly seeded engine back in place when dropped.
+struct RestoreRandomEngine;
+
+impl Drop for RestoreRandomEngine {
+    fn drop(&mut self) {
+        BooleanEngine::replace_thread_local(BooleanEngine::new());
+    }
+}
@@ -0,0 +1,141 @@
+/*!
+Runtime control over the threads used for encryption and evaluation.
+
+By default work runs on rayon's global thread pool (or sequentially when the
+`parallel` feature is disabled). A server hosting several tenants usually
+wants to cap or isolate the CPU used per request instead, which is what an
+[`ExecutionConfig`] is for. It can be passed to
+[`Circuit::encrypt_inputs_with_config`],
+[`EncryptedCircuit::try_evaluate_with_config`] and
+[`EncryptedCircuit::try_evaluate_batch`].
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+use encircuit::ExecutionConfig;
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, server_key) = Keyset::generate(&params)?.split();
+# let mut builder = CircuitBuilder::default();
+# let x = builder.input();
+# let y = builder.input();
+# let out = builder.and(x, y);
+# let circuit = builder.finish(out);
+// Give this request at most four threads
+let config = ExecutionConfig::threads(4)?;
+
+let encrypted = circuit.encrypt_inputs_with_config(&[true, false], &client_key, &config)?;
+let result = encrypted.try_evaluate_with_config(&server_key, &config)?;
+# Ok(())
+# }
+```
+
+[`Circuit::encrypt_inputs_with_config`]: crate::Circuit::encrypt_inputs_with_config
+[`EncryptedCircuit::try_evaluate_with_config`]: crate::EncryptedCircuit::try_evaluate_with_config
+[`EncryptedCircuit::try_evaluate_batch`]: crate::EncryptedCircuit::try_evaluate_batch
+*/
+
+use anyhow::Result;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Where the work of encryption and evaluation runs.
+///
+/// The configuration is a runtime choice: the same binary can evaluate one
+/// request sequentially and the next on a dedicated pool. Cloning an
+/// `ExecutionConfig` shares the underlying thread pool.
+#[derive(Debug, Clone)]
+pub struct ExecutionConfig {
+    mode: Mode,
+}
+
+#[derive(Debug, Clone)]
+enum Mode {
+    Sequential,
+    Global,
+    Pool(Arc<rayon::ThreadPool>),
+}
+
+impl ExecutionConfig {
+    /// Run everything on the calling thread.
+    pub fn sequential() -> Self {
+        Self {
+            mode: Mode::Sequential,
+        }
+    }
+
+    /// Run on rayon's global thread pool.
+    pub fn global() -> Self {
+        Self { mode: Mode::Global }
+    }
+
+    /// Run on a dedicated pool with `threads` worker threads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `threads` is zero or the pool cannot be created.
+    pub fn threads(threads: usize) -> Result<Self> {
+        if threads == 0 {
+            anyhow::bail!("Thread count must be greater than zero");
+        }
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to create thread pool: {}", e))?;
+        Ok(Self::with_pool(Arc::new(pool)))
+    }
+
+    /// Run on a caller-provided rayon thread pool.
+    ///
+    /// Sharing one pool between several configurations caps their combined
+    /// CPU usage.
+    pub fn with_pool(pool: Arc<rayon::ThreadPool>) -> Self {
+        Self {
+            mode: Mode::Pool(pool),
+        }
+    }
+
+    /// Check whether work runs on the calling thread only.
+    pub fn is_sequential(&self) -> bool {
+        matches!(self.mode, Mode::Sequential)
+    }
+
+    /// Get the maximum number of threads work may run on.
+    pub fn thread_count(&self) -> usize {
+        match &self.mode {
+            Mode::Sequential => 1,
+            Mode::Global => rayon::current_num_threads(),
+            Mode::Pool(pool) => pool.current_num_threads(),
+        }
+    }
+
+    /// Apply `f` to every item, in parallel unless the configuration is sequential.
+    ///
+    /// The results are returned in the order of `items`.
+    pub(crate) fn map<T, R, F>(&self, items: &[T], f: F) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        F: Fn(&T) -> R + Sync + Send,
+    {
+        match &self.mode {
+            Mode::Sequential => items.iter().map(f).collect(),
+            Mode::Global => items.par_iter().map(f).collect(),
+            Mode::Pool(pool) => pool.install(|| items.par_iter().map(f).collect()),
+        }
+    }
+}
+
+impl Default for ExecutionConfig {
+    /// Use rayon's global pool when the `parallel` feature is enabled, and
+    /// sequential execution otherwise.
+    fn default() -> Self {
+        if cfg!(feature = "parallel") {
+            Self::global()
+        } else {
+            Self::sequential()
+        }
+    }
+}
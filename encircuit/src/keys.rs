@@ -27,10 +27,12 @@ let keyset = Keyset::generate(&params)?;
 
 ## Parameter Selection Details
 
-The key generation automatically selects appropriate TFHE parameters based on:
+The key generation uses the TFHE parameters held by the `Params`, which come from:
 
-1. **Scenario** (if specified): Uses predefined parameter sets optimized for common use cases
-2. **Security Level** (fallback): Uses general parameter sets based on security requirements
+1. **Scenario**: Predefined parameter sets optimized for common use cases
+2. **Security targets**: `Params::for_security` picks the least costly predefined set
+   meeting a security level and maximum error probability
+3. **Custom parameters**: `Params::custom` accepts a raw TFHE parameter set
 
 Available scenarios:
 - **SafeAndBalanced**: DEFAULT_PARAMETERS - good security/performance balance
@@ -38,6 +40,36 @@ Available scenarios:
 - **FastDemo**: DEFAULT_PARAMETERS_KS_PBS - optimized for speed
 - **MaxSecurityMinimalOps**: PARAMETERS_ERROR_PROB_2_POW_MINUS_165_KS_PBS - maximum security
 
+## Compressed Server Keys
+
+Server keys are tens of megabytes. [`Keyset::generate_compressed`] returns the
+server key as a [`CompressedServerKeyBytes`], which is much smaller to ship and
+is expanded on the evaluation node:
+
+```rust,no_run
+use encircuit::prelude::*;
+# fn main() -> anyhow::Result<()> {
+let params = Params::for_scenario(Scenario::SafeAndBalanced)?;
+let (client_key, compressed) = Keyset::generate_compressed(&params)?;
+
+// Upload `compressed.as_bytes()?` to the evaluation node, then there:
+let server_key = compressed.decompress();
+# Ok(())
+# }
+```
+
+## Public Keys
+
+Data producers that must be able to encrypt but never decrypt get a
+[`PublicKeyBytes`] derived with [`ClientKeyBytes::public_key`], and encrypt
+circuit inputs with `Circuit::encrypt_inputs_with_public_key`.
+
+## Key Ids
+
+Every key carries the [`KeyId`] of its keyset, and ciphertexts carry the id of
+the key that encrypted them. Evaluating or decrypting with a key of another
+keyset fails with an error instead of producing garbage.
+
 ## Serialization
 
 Key serialization is supported when the `serde` feature is enabled. The implementation
@@ -49,17 +81,70 @@ uses binary serialization via `bincode` with TFHE's built-in serde support.
 - Round-trip serialization (serialize -> deserialize) is guaranteed to preserve key functionality
 - Keys serialized without the `serde` feature will fail at runtime with appropriate error messages
 
+Server keys take tens to hundreds of megabytes. [`ServerKeyBytes::read_from`]
+and [`ServerKeyBytes::write_to`] stream them from and to files or sockets
+without an intermediate buffer, so loading a key doesn't need twice its size
+in memory. With the `mmap` feature, [`ServerKeyBytes::from_mapped_file`]
+decodes a key straight from a memory-mapped file.
+
+```rust,no_run
+use encircuit::prelude::*;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (_, server_key) = Keyset::generate(&params)?.split();
+server_key.write_to(BufWriter::new(File::create("server.key")?))?;
+
+let server_key = ServerKeyBytes::read_from(BufReader::new(File::open("server.key")?))?;
+# Ok(())
+# }
+```
+
+## Passphrase Protection
+
+[`ClientKeyBytes::to_encrypted_bytes`] encrypts the serialized client key under a
+passphrase, for storing it at rest:
+
+```rust,no_run
+use encircuit::prelude::*;
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, _) = Keyset::generate(&params)?.split();
+let sealed = client_key.to_encrypted_bytes("correct horse battery staple")?;
+std::fs::write("client.key", &sealed)?;
+
+let client_key = ClientKeyBytes::from_encrypted_bytes(&std::fs::read("client.key")?, "correct horse battery staple")?;
+# Ok(())
+# }
+```
+
 ## Security Considerations
 
 - Client keys should be kept secure and never transmitted over untrusted channels
+- Prefer `to_encrypted_bytes` over `as_bytes` when persisting client keys
 - Server keys can be safely transmitted to computation servers
+- Public keys can be safely handed to data producers; they only allow encryption
 - Serialized keys contain the same cryptographic material as the original keys
+- Client keys and their serialized [`SecretBytes`] are zeroized when dropped;
+  clones of a `ClientKeyBytes` share one copy of the secret key
 */
 
+use crate::ciphertext::BoolCt;
+use crate::deserialization::{self, DeserializationConfig};
 use crate::params::Params;
+use crate::secret::SecretBytes;
+use crate::sharing::KeyShare;
 use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::{Read, Write};
+use std::sync::Arc;
+use zeroize::Zeroize;
 
 use tfhe::boolean::prelude;
+use tfhe::boolean::public_key::CompressedPublicKey;
+use tfhe::boolean::server_key::CompressedServerKey;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -79,10 +164,57 @@ pub struct Keyset {
 ///
 /// This type wraps the client key in a form that can be easily serialized
 /// and transmitted between different parts of an application.
+///
+/// Clones share a single copy of the secret key, which is zeroized when the
+/// last clone is dropped.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ClientKeyBytes {
-    key: prelude::ClientKey
+    #[cfg_attr(feature = "serde", serde(with = "shared_client_key"))]
+    key: Arc<SecretClientKey>,
+    id: KeyId,
+}
+
+/// A TFHE client key whose secret coefficients are zeroized on drop.
+///
+/// The key is only `None` while being dropped.
+struct SecretClientKey(Option<prelude::ClientKey>);
+
+impl SecretClientKey {
+    fn new(key: prelude::ClientKey) -> Arc<Self> {
+        Arc::new(Self(Some(key)))
+    }
+
+    fn get(&self) -> &prelude::ClientKey {
+        self.0.as_ref().expect("client key is present until dropped")
+    }
+}
+
+impl Drop for SecretClientKey {
+    fn drop(&mut self) {
+        if let Some(key) = self.0.take() {
+            let (mut lwe_secret_key, mut glwe_secret_key, _) = key.into_raw_parts();
+            lwe_secret_key.as_mut().zeroize();
+            glwe_secret_key.as_mut().zeroize();
+        }
+    }
+}
+
+/// Serializes the shared client key as a plain TFHE client key.
+#[cfg(feature = "serde")]
+mod shared_client_key {
+    use super::SecretClientKey;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::sync::Arc;
+    use tfhe::boolean::prelude::ClientKey;
+
+    pub(super) fn serialize<S: Serializer>(key: &Arc<SecretClientKey>, serializer: S) -> Result<S::Ok, S::Error> {
+        key.get().serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<SecretClientKey>, D::Error> {
+        ClientKey::deserialize(deserializer).map(SecretClientKey::new)
+    }
 }
 
 /// Serialized server key bytes.
@@ -92,18 +224,87 @@ pub struct ClientKeyBytes {
 #[derive(Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ServerKeyBytes {
-    key: prelude::ServerKey
+    key: prelude::ServerKey,
+    id: KeyId,
+}
+
+/// Serialized compressed server key bytes.
+///
+/// A compressed server key stores the seeds of its random masks instead of
+/// the masks themselves, which makes it several times smaller than a
+/// [`ServerKeyBytes`]. Ship it to evaluation nodes and expand it there with
+/// [`CompressedServerKeyBytes::decompress`].
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CompressedServerKeyBytes {
+    key: CompressedServerKey,
+    id: KeyId,
+}
+
+/// Serialized public key bytes.
+///
+/// A public key encrypts but cannot decrypt, so it can be handed to data
+/// producers that must never see results. It is derived from a client key
+/// with [`ClientKeyBytes::public_key`] and kept in TFHE's compressed form,
+/// which stays small enough to ship to constrained devices.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PublicKeyBytes {
+    key: CompressedPublicKey,
+    id: KeyId,
+}
+
+/// Identifies the keyset a key or ciphertext belongs to.
+///
+/// Every key of a keyset carries the same id, and ciphertexts carry the id
+/// of the key that encrypted them. Evaluating or decrypting a ciphertext with
+/// a key of another keyset, including one generated with other parameters,
+/// fails with an error naming both ids instead of silently producing garbage.
+///
+/// The id is a truncated SHA-256 hash of the client key's encryption secret
+/// and reveals nothing about it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KeyId([u8; 16]);
+
+impl KeyId {
+    /// Compute the id of a client key.
+    fn of(client_key: &prelude::ClientKey) -> Self {
+        let (secret, _) = client_key.encryption_key_and_noise();
+        let mut hasher = Sha256::new();
+        hasher.update(b"encircuit/key-id/v1");
+        for coefficient in secret.as_ref() {
+            hasher.update(coefficient.to_le_bytes());
+        }
+
+        let digest = hasher.finalize();
+        Self(digest[..16].try_into().expect("digest is 32 bytes"))
+    }
+
+    /// Get the raw id bytes.
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl fmt::Display for KeyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
 }
 
 impl Keyset {
     /// Generate a new keyset from the given parameters.
     ///
-    /// This method uses the scenario-based parameter selection from the `Params`
-    /// to choose appropriate TFHE parameters for key generation.
+    /// This method uses the TFHE parameters held by `Params`, either selected
+    /// from a scenario or provided with [`Params::custom`].
     ///
     /// # Parameters
     ///
-    /// * `params` - FHE parameters specifying scenario and supported operations
+    /// * `params` - FHE parameters specifying the TFHE parameter set and supported operations
     ///
     /// # Errors
     ///
@@ -116,7 +317,7 @@ impl Keyset {
             anyhow::bail!("Boolean operations not supported by the provided parameters");
         }
 
-        // Get TFHE parameters using the scenario-based selection
+        // Get the TFHE parameters, from a scenario or a custom set
         let tfhe_params = params.tfhe_boolean_params();
         
         // Generate client key
@@ -125,15 +326,77 @@ impl Keyset {
         // Derive server key from client key
         let server_key = prelude::ServerKey::new(&client_key);
 
+        let id = KeyId::of(&client_key);
         Ok(Self {
-            client_key: ClientKeyBytes { key: client_key },
-            server_key: ServerKeyBytes { key: server_key },
+            client_key: ClientKeyBytes { key: SecretClientKey::new(client_key), id },
+            server_key: ServerKeyBytes { key: server_key, id },
         })
     }
 
+    /// Generate a keyset deterministically from a seed.
+    ///
+    /// The same seed and parameters always produce byte-identical keys, which
+    /// lets golden-file and regression tests pin keys. **Insecure**: anyone
+    /// knowing the seed can regenerate the client key. Only available with
+    /// the `insecure-deterministic-rng` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Boolean operations are not supported by the provided parameters
+    /// - Key generation fails
+    #[cfg(feature = "insecure-deterministic-rng")]
+    pub fn generate_from_seed(params: &Params, seed: [u8; 32]) -> Result<Self> {
+        crate::deterministic::with_seeded_engine(seed, || Self::generate(params))
+    }
+
+    /// Generate a client key and a compressed server key from the given parameters.
+    ///
+    /// This is the transport-friendly counterpart of [`Keyset::generate`]: the
+    /// server key is returned in compressed form, to be expanded on the
+    /// evaluation node with [`CompressedServerKeyBytes::decompress`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Boolean operations are not supported by the provided parameters
+    /// - Key generation fails
+    pub fn generate_compressed(params: &Params) -> Result<(ClientKeyBytes, CompressedServerKeyBytes)> {
+        if !params.supports_boolean() {
+            anyhow::bail!("Boolean operations not supported by the provided parameters");
+        }
+
+        let client_key = prelude::ClientKey::new(params.tfhe_boolean_params());
+        let server_key = CompressedServerKey::new(&client_key);
+
+        let id = KeyId::of(&client_key);
+        Ok((
+            ClientKeyBytes { key: SecretClientKey::new(client_key), id },
+            CompressedServerKeyBytes { key: server_key, id },
+        ))
+    }
+
+    /// Assemble a keyset from a client key and its server key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keys belong to different keysets.
+    #[cfg(feature = "key-cache")]
+    pub(crate) fn from_keys(client_key: ClientKeyBytes, server_key: ServerKeyBytes) -> Result<Self> {
+        if client_key.id() != server_key.id() {
+            anyhow::bail!(
+                "Client key {} and server key {} belong to different keysets",
+                client_key.id(),
+                server_key.id()
+            );
+        }
+        Ok(Self { client_key, server_key })
+    }
+
     /// Split the keyset into separate client and server keys.
     ///
-    /// Returns a tuple of (client_key, server_key).
+    /// Returns a tuple of (client_key, server_key). The keys are moved out,
+    /// so no copy of the secret key is made.
     pub fn split(self) -> (ClientKeyBytes, ServerKeyBytes) {
         (self.client_key, self.server_key)
     }
@@ -163,13 +426,16 @@ impl ClientKeyBytes {
     /// Returns an error if:
     /// - The `serde` feature is not enabled
     /// - The input data cannot be deserialized as a valid TFHE ClientKey
-    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+    ///
+    /// The input buffer is zeroized once the key has been read.
+    pub fn from_bytes(data: impl Into<SecretBytes>) -> Result<Self> {
+        let data: SecretBytes = data.into();
         #[cfg(feature = "serde")]
         {
             // Deserialize using bincode for TFHE keys
-            let key: prelude::ClientKey = bincode::deserialize(&data)
+            let client_key: Self = bincode::deserialize(&data)
                 .map_err(|e| anyhow::anyhow!("Failed to deserialize client key: {}", e))?;
-            Ok(Self { key })
+            client_key.verify_id()
         }
         #[cfg(not(feature = "serde"))]
         {
@@ -178,23 +444,76 @@ impl ClientKeyBytes {
         }
     }
 
+    /// Create a client key from untrusted bytes, within the limits of `config`.
+    ///
+    /// Unlike `from_bytes`, this rejects data larger than the configured size
+    /// limit and, if the configuration names expected parameters, keys
+    /// that don't conform to them. Use it for data from untrusted sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - The data exceeds the size limit or cannot be deserialized
+    /// - The key doesn't conform to the expected parameters
+    pub fn safe_from_bytes(data: &[u8], config: &DeserializationConfig) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            let client_key = config.deserialize::<Self>(data, "client key")?.verify_id()?;
+            if let Some(params) = config.expected_params() {
+                client_key.check_conformance(params)?;
+            }
+            Ok(client_key)
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = (data, config); // Suppress unused warning
+            anyhow::bail!("Serde feature required for key deserialization")
+        }
+    }
+
+    /// Check that the key was generated for `params`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first dimension or noise setting that
+    /// differs from the parameters.
+    pub fn check_conformance(&self, params: &Params) -> Result<()> {
+        deserialization::check_client_key(self.key.get(), params)
+            .map_err(|e| anyhow::anyhow!("Client key doesn't conform to the parameters: {}", e))
+    }
+
+    /// Check that the stored key id matches the key, as deserialized ids are untrusted.
+    #[cfg(feature = "serde")]
+    fn verify_id(self) -> Result<Self> {
+        if KeyId::of(self.key.get()) != self.id {
+            anyhow::bail!("Failed to deserialize client key: key id {} doesn't match the key", self.id);
+        }
+        Ok(self)
+    }
+
     /// Get the raw bytes of the client key.
     ///
     /// The returned bytes contain a binary representation of the TFHE ClientKey
     /// that can be stored or transmitted and later reconstructed using
-    /// [`ClientKeyBytes::from_bytes`].
+    /// [`ClientKeyBytes::from_bytes`]. The bytes are zeroized when dropped.
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - The `serde` feature is not enabled
     /// - Binary serialization fails (very unlikely for valid keys)
-    pub fn as_bytes(&self) -> Result<Vec<u8>> {
+    pub fn as_bytes(&self) -> Result<SecretBytes> {
         #[cfg(feature = "serde")]
         {
-            // Serialize using bincode for TFHE keys
-            bincode::serialize(&self.key)
-                .map_err(|e| anyhow::anyhow!("Failed to serialize client key: {}", e))
+            // Serialize into an exactly sized buffer, so that no reallocation
+            // leaves an unwiped partial copy behind
+            let size = bincode::serialized_size(self)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize client key: {}", e))?;
+            let mut bytes = SecretBytes::new(Vec::with_capacity(size as usize));
+            bincode::serialize_into(bytes.buffer(), self)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize client key: {}", e))?;
+            Ok(bytes)
         }
         #[cfg(not(feature = "serde"))]
         {
@@ -202,9 +521,105 @@ impl ClientKeyBytes {
         }
     }
 
+    /// Encrypt the client key under a passphrase for storage at rest.
+    ///
+    /// The key is serialized as with [`ClientKeyBytes::as_bytes`] and
+    /// encrypted with ChaCha20-Poly1305 under a key derived from the
+    /// passphrase with Argon2id. The container is versioned and
+    /// authenticated; restore it with [`ClientKeyBytes::from_encrypted_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - The passphrase is empty
+    pub fn to_encrypted_bytes(&self, passphrase: &str) -> Result<Vec<u8>> {
+        crate::passphrase::seal(&self.as_bytes()?, passphrase)
+    }
+
+    /// Restore a client key encrypted with [`ClientKeyBytes::to_encrypted_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The data is not an encrypted client key or uses an unsupported format version
+    /// - The passphrase is wrong or the data was tampered with
+    /// - The `serde` feature is not enabled
+    pub fn from_encrypted_bytes(data: &[u8], passphrase: &str) -> Result<Self> {
+        let bytes = crate::passphrase::open(data, passphrase)
+            .map_err(|e| anyhow::anyhow!("Failed to decrypt client key: {}", e))?;
+        Self::from_bytes(bytes)
+    }
+
+    /// Split the client key into `count` shares, any `threshold` of which restore it.
+    ///
+    /// Fewer than `threshold` shares reveal nothing about the key, so each
+    /// share can be held by a different operator. Restore the key with
+    /// [`ClientKeyBytes::from_shares`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - `threshold` is less than 2 or greater than `count`
+    pub fn split_into_shares(&self, threshold: u8, count: u8) -> Result<Vec<KeyShare>> {
+        crate::sharing::split(&self.as_bytes()?, self.id, threshold, count)
+    }
+
+    /// Restore a client key from shares created with [`ClientKeyBytes::split_into_shares`].
+    ///
+    /// Any `threshold` distinct shares of the same split restore the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the offending share if a share is corrupted,
+    /// duplicated, belongs to another split or doesn't match the other
+    /// shares, and an error if there are fewer shares than the threshold.
+    pub fn from_shares(shares: &[KeyShare]) -> Result<Self> {
+        let (id, bytes) = crate::sharing::combine(shares)
+            .map_err(|e| anyhow::anyhow!("Failed to restore client key from shares: {}", e))?;
+        let client_key = Self::from_bytes(bytes)?;
+        if client_key.id != id {
+            anyhow::bail!(
+                "Failed to restore client key from shares: restored key {} doesn't match the shares' key {}",
+                client_key.id,
+                id
+            );
+        }
+        Ok(client_key)
+    }
+
+    /// Derive the public key matching this client key.
+    ///
+    /// Ciphertexts encrypted with the public key decrypt with this client key
+    /// and evaluate with the matching server key.
+    pub fn public_key(&self) -> PublicKeyBytes {
+        PublicKeyBytes {
+            key: CompressedPublicKey::new(self.key.get()),
+            id: self.id,
+        }
+    }
+
+    /// Get the id of the keyset this key belongs to.
+    pub fn id(&self) -> KeyId {
+        self.id
+    }
+
+    /// Check that a ciphertext was encrypted under this keyset.
+    pub(crate) fn check_ciphertext(&self, ciphertext: &BoolCt) -> Result<()> {
+        match ciphertext.key_id() {
+            Some(id) if id != self.id => anyhow::bail!(
+                "Ciphertext was encrypted under key {} but the client key is {}",
+                id,
+                self.id
+            ),
+            _ => Ok(()),
+        }
+    }
+
     /// Get the TFHE client key for encryption/decryption operations.
     pub fn tfhe_key(&self) -> Result<&prelude::ClientKey> {
-        Ok(&self.key)
+        Ok(self.key.get())
     }
 }
 
@@ -225,9 +640,8 @@ impl ServerKeyBytes {
         #[cfg(feature = "serde")]
         {
             // Deserialize using bincode for TFHE keys
-            let key: prelude::ServerKey = bincode::deserialize(&data)
-                .map_err(|e| anyhow::anyhow!("Failed to deserialize server key: {}", e))?;
-            Ok(Self { key })
+            bincode::deserialize(&data)
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize server key: {}", e))
         }
         #[cfg(not(feature = "serde"))]
         {
@@ -236,6 +650,94 @@ impl ServerKeyBytes {
         }
     }
 
+    /// Read a server key written by [`ServerKeyBytes::write_to`] or [`ServerKeyBytes::as_bytes`].
+    ///
+    /// The key is decoded while reading, without buffering the serialized
+    /// form. Many small reads are made, so wrap files and sockets in a
+    /// [`BufReader`](std::io::BufReader).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - Reading fails or the data cannot be deserialized as a server key
+    pub fn read_from(reader: impl Read) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::deserialize_from(reader)
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize server key: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = reader; // Suppress unused warning
+            anyhow::bail!("Serde feature required for key deserialization")
+        }
+    }
+
+    /// Load a server key from a memory-mapped file.
+    ///
+    /// The key is decoded directly from the mapped pages, which the operating
+    /// system can page in on demand and drop afterwards, instead of from a
+    /// heap copy of the file.
+    ///
+    /// The file must not be modified while it is being loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or mapped, or does not
+    /// contain a server key.
+    #[cfg(feature = "mmap")]
+    pub fn from_mapped_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open server key {}: {}", path.display(), e))?;
+        // SAFETY: the mapping is only read while decoding and dropped before
+        // returning; callers must not modify the file during that time
+        let map = unsafe { memmap2::Mmap::map(&file) }
+            .map_err(|e| anyhow::anyhow!("Failed to map server key {}: {}", path.display(), e))?;
+        bincode::deserialize(&map)
+            .map_err(|e| anyhow::anyhow!("Failed to deserialize server key {}: {}", path.display(), e))
+    }
+
+    /// Create a server key from untrusted bytes, within the limits of `config`.
+    ///
+    /// Unlike `from_bytes`, this rejects data larger than the configured size
+    /// limit and, if the configuration names expected parameters, keys
+    /// that don't conform to them. Use it for data from untrusted sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - The data exceeds the size limit or cannot be deserialized
+    /// - The key doesn't conform to the expected parameters
+    pub fn safe_from_bytes(data: &[u8], config: &DeserializationConfig) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            let server_key: Self = config.deserialize(data, "server key")?;
+            if let Some(params) = config.expected_params() {
+                server_key.check_conformance(params)?;
+            }
+            Ok(server_key)
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = (data, config); // Suppress unused warning
+            anyhow::bail!("Serde feature required for key deserialization")
+        }
+    }
+
+    /// Check that the key was generated for `params`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bootstrapping or key switching key has other
+    /// dimensions than the parameters produce.
+    pub fn check_conformance(&self, params: &Params) -> Result<()> {
+        deserialization::check_server_key(&self.key, params)
+            .map_err(|e| anyhow::anyhow!("Server key doesn't conform to the parameters: {}", e))
+    }
+
     /// Get the raw bytes of the server key.
     ///
     /// The returned bytes contain a binary representation of the TFHE ServerKey
@@ -251,7 +753,7 @@ impl ServerKeyBytes {
         #[cfg(feature = "serde")]
         {
             // Serialize using bincode for TFHE keys
-            bincode::serialize(&self.key)
+            bincode::serialize(self)
                 .map_err(|e| anyhow::anyhow!("Failed to serialize server key: {}", e))
         }
         #[cfg(not(feature = "serde"))]
@@ -260,15 +762,179 @@ impl ServerKeyBytes {
         }
     }
 
+    /// Write the server key, in the format of [`ServerKeyBytes::as_bytes`].
+    ///
+    /// The key is encoded while writing, without buffering the serialized
+    /// form. Many small writes are made, so wrap files and sockets in a
+    /// [`BufWriter`](std::io::BufWriter).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - Writing fails
+    pub fn write_to(&self, writer: impl Write) -> Result<()> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::serialize_into(writer, self)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize server key: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = writer; // Suppress unused warning
+            anyhow::bail!("Serde feature required for key serialization")
+        }
+    }
+
+    /// Get the id of the keyset this key belongs to.
+    pub fn id(&self) -> KeyId {
+        self.id
+    }
+
+    /// Check that a ciphertext was encrypted under this keyset.
+    ///
+    /// Ciphertexts without a key id, such as trivial encryptions, pass.
+    /// `describe` names the ciphertext in the error, e.g. `"Ciphertext for input 2"`.
+    pub(crate) fn check_ciphertext(&self, ciphertext: &BoolCt, describe: impl FnOnce() -> String) -> Result<()> {
+        match ciphertext.key_id() {
+            Some(id) if id != self.id => anyhow::bail!(
+                "{} was encrypted under key {} but the server key is {}",
+                describe(),
+                id,
+                self.id
+            ),
+            _ => Ok(()),
+        }
+    }
+
     /// Get the TFHE server key for homomorphic operations.
     pub fn tfhe_key(&self) -> Result<&prelude::ServerKey> {
         Ok(&self.key)
     }
 }
 
+impl CompressedServerKeyBytes {
+    /// Create a new compressed server key from raw bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - The input data cannot be deserialized as a valid TFHE CompressedServerKey
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::deserialize(&data)
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize compressed server key: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = data; // Suppress unused warning
+            anyhow::bail!("Serde feature required for key deserialization")
+        }
+    }
+
+    /// Get the raw bytes of the compressed server key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - Binary serialization fails (very unlikely for valid keys)
+    pub fn as_bytes(&self) -> Result<Vec<u8>> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::serialize(self)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize compressed server key: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            anyhow::bail!("Serde feature required for key serialization")
+        }
+    }
+
+    /// Get the id of the keyset this key belongs to.
+    pub fn id(&self) -> KeyId {
+        self.id
+    }
+
+    /// Expand the compressed key into a server key usable for evaluation.
+    ///
+    /// Decompression regenerates the random masks from their seeds, which
+    /// takes a noticeable fraction of the time key generation takes; do it
+    /// once per evaluation node and keep the result.
+    pub fn decompress(&self) -> ServerKeyBytes {
+        ServerKeyBytes {
+            key: self.key.decompress(),
+            id: self.id,
+        }
+    }
+}
+
+impl PublicKeyBytes {
+    /// Create a new public key from raw bytes.
+    ///
+    /// # Security
+    ///
+    /// Public keys are safe to transmit over untrusted channels, as they do
+    /// not allow decryption of ciphertexts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - The input data cannot be deserialized as a valid TFHE CompressedPublicKey
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::deserialize(&data)
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize public key: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = data; // Suppress unused warning
+            anyhow::bail!("Serde feature required for key deserialization")
+        }
+    }
+
+    /// Get the raw bytes of the public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The `serde` feature is not enabled
+    /// - Binary serialization fails (very unlikely for valid keys)
+    pub fn as_bytes(&self) -> Result<Vec<u8>> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::serialize(self)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize public key: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            anyhow::bail!("Serde feature required for key serialization")
+        }
+    }
+
+    /// Get the id of the keyset this key belongs to.
+    pub fn id(&self) -> KeyId {
+        self.id
+    }
+
+    /// Encrypt a Boolean value.
+    ///
+    /// Every encryption expands the compressed key on the fly, so it costs
+    /// considerably more than encrypting with a client key, but needs no
+    /// more memory than the compressed key itself.
+    pub fn encrypt(&self, value: bool) -> BoolCt {
+        BoolCt::from_tfhe_ciphertext(self.key.encrypt(value)).with_key_id(Some(self.id))
+    }
+}
+
 impl std::fmt::Debug for ClientKeyBytes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ClientKeyBytes")
+            .field("id", &format_args!("{}", self.id))
             .field("encrypted", &"<tfhe_client_key>")
             .finish()
     }
@@ -277,7 +943,26 @@ impl std::fmt::Debug for ClientKeyBytes {
 impl std::fmt::Debug for ServerKeyBytes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ServerKeyBytes")
+            .field("id", &format_args!("{}", self.id))
             .field("encrypted", &"<tfhe_server_key>")
             .finish()
     }
 }
+
+impl std::fmt::Debug for PublicKeyBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublicKeyBytes")
+            .field("id", &format_args!("{}", self.id))
+            .field("encrypted", &"<tfhe_public_key>")
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for CompressedServerKeyBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompressedServerKeyBytes")
+            .field("id", &format_args!("{}", self.id))
+            .field("encrypted", &"<tfhe_compressed_server_key>")
+            .finish()
+    }
+}
@@ -86,19 +86,46 @@ let decrypted = result[0].decrypt(&client_key)?;
 
 pub mod prelude;
 
+mod backend;
+#[cfg(feature = "key-cache")]
+mod cache;
 mod ciphertext;
 mod circuit;
+#[cfg(feature = "insecure-deterministic-rng")]
+mod deterministic;
+mod deserialization;
+mod execution;
 mod keys;
 mod params;
+mod passphrase;
+mod secret;
+mod sharing;
 
 #[cfg(test)]
 mod tests;
 
 // Public re-exports
+pub use backend::{Backend, MockBackend, MockCiphertext, PlaintextBackend, TfheBackend};
 pub use ciphertext::BoolCt;
-pub use circuit::{Circuit, CircuitBuilder, CircuitComplexity, EncryptedCircuit};
-pub use keys::{ClientKeyBytes, Keyset, ServerKeyBytes};
-pub use params::{Params, Scenario};
+pub use circuit::{
+    CancellationToken, Circuit, CircuitBuilder, CircuitComplexity, EncryptedCircuit,
+    EvaluationCheckpoint, EvaluationProfile, EvaluationProgress, GateTiming, IncrementalEvaluator,
+    InputAssembly, InputContribution, InputPort, ProfileGroup, RedundancyCost, RedundancyPlan,
+    RedundantCircuit,
+};
+#[cfg(feature = "key-cache")]
+pub use cache::KeyCache;
+#[cfg(feature = "debug-oracle")]
+pub use circuit::{DebugReport, GateDivergence};
+pub use deserialization::DeserializationConfig;
+pub use execution::ExecutionConfig;
+pub use keys::{ClientKeyBytes, CompressedServerKeyBytes, KeyId, Keyset, PublicKeyBytes, ServerKeyBytes};
+pub use params::{
+    CandidateAssessment, ErrorProbability, ParameterSetInfo, Params, ParamsFingerprint, ParamsReport,
+    Recommendation, Requirements, Scenario, SecurityLevel,
+};
+pub use secret::SecretBytes;
+pub use sharing::KeyShare;
 
 // Conditional compile for macros
 #[cfg(feature = "macros")]
@@ -14,6 +14,29 @@ which maps common use cases to appropriate TFHE parameter sets:
 - **FastDemo**: Quick prototyping with minimal security requirements
 - **MaxSecurityMinimalOps**: Maximum security for minimal operations
 
+## Custom Parameters
+
+Parameter sets tuned by cryptographers can be used through [`Params::custom`],
+which takes raw TFHE `BooleanParameters` and checks them for structural
+sanity. Key generation works the same way for custom and scenario parameters.
+
+## Security-Driven Parameter Selection
+
+[`Params::for_security`] picks the least costly built-in parameter set that
+meets both a [`SecurityLevel`] and a maximum bootstrapping [`ErrorProbability`],
+and explains the choice in a [`ParamsReport`].
+
+[`Params::recommend_for`] applies the same idea to a whole [`Circuit`]: it
+bounds the probability that any of the circuit's bootstraps fails, and keeps
+it under the target set in [`Requirements`].
+
+## Configuration Files
+
+With the `serde` feature, [`Params`] can be stored in configuration, including
+custom parameter sets. The `config` feature adds [`Params::from_toml_str`] and
+[`Params::from_json_str`], which validate what they load. A [`ParamsFingerprint`]
+identifies a parameter set and can be stored next to keys and ciphertexts.
+
 ## Example
 
 ```rust,no_run
@@ -27,19 +50,45 @@ let keyset = Keyset::generate(&params)?;
 // Different scenarios for different use cases
 let demo_params = Params::for_scenario(Scenario::FastDemo)?;
 let secure_params = Params::for_scenario(Scenario::MaxSecurityMinimalOps)?;
+
+// Parameter sets tuned by a cryptographer
+let mut tuned = *demo_params.tfhe_boolean_params();
+tuned.pbs_level = tfhe::boolean::parameters::DecompositionLevelCount(3);
+let custom_params = Params::custom(tuned)?;
+
+// The cheapest parameter set meeting explicit targets
+let report = Params::for_security(SecurityLevel::Bits128, ErrorProbability::Pow2Minus64)?;
+println!("{}", report);
+let params = report.into_params();
+
+// The cheapest parameter set keeping a whole circuit under an error target
+# let mut builder = CircuitBuilder::default();
+# let x = builder.input();
+# let y = builder.input();
+# let out = builder.and(x, y);
+# let circuit = builder.finish(out);
+let recommendation = Params::recommend_for(&circuit, Requirements::default())?;
+println!("{}", recommendation);
 # Ok(())
 # }
 ```
 */
 
+use crate::circuit::Circuit;
 use anyhow::Result;
-use tfhe::boolean::parameters::BooleanParameters;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+use tfhe::boolean::parameters::{BooleanParameters, DynamicDistribution, EncryptionKeyChoice};
 
 /// Describes typical TFHE usage scenarios with different security/performance trade-offs.
 ///
 /// Each scenario maps to a specific set of TFHE Boolean parameters that have been
 /// carefully selected for that use case.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Scenario {
     /// Safe and balanced use (default for general-purpose circuits).
     ///
@@ -92,10 +141,53 @@ pub fn scenario_to_tfhe_params(scenario: Scenario) -> &'static BooleanParameters
 /// FHE parameter configuration.
 ///
 /// Provides opinionated presets for common FHE configurations using scenario-based
-/// parameter selection.
+/// parameter selection, and accepts custom TFHE parameter sets through
+/// [`Params::custom`].
+///
+/// With the `serde` feature, scenario parameters serialize as the scenario name
+/// (`scenario = "FastDemo"` in TOML) and custom parameters as the full TFHE
+/// parameter set under a `custom` key. Custom parameter sets are validated
+/// again when deserialized.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "ParamsRepr", into = "ParamsRepr")
+)]
 pub struct Params {
-    scenario: Scenario,
+    scenario: Option<Scenario>,
+    tfhe_params: BooleanParameters,
+}
+
+/// The serialized form of [`Params`].
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ParamsRepr {
+    Scenario(Scenario),
+    Custom(BooleanParameters),
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ParamsRepr> for Params {
+    type Error = anyhow::Error;
+
+    fn try_from(repr: ParamsRepr) -> Result<Self> {
+        match repr {
+            ParamsRepr::Scenario(scenario) => Self::for_scenario(scenario),
+            ParamsRepr::Custom(tfhe_params) => Self::custom(tfhe_params),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Params> for ParamsRepr {
+    fn from(params: Params) -> Self {
+        match params.scenario {
+            Some(scenario) => Self::Scenario(scenario),
+            None => Self::Custom(params.tfhe_params),
+        }
+    }
 }
 
 impl Params {
@@ -104,13 +196,199 @@ impl Params {
     /// This creates parameters optimized for the specified scenario with Boolean operations.
     pub fn for_scenario(scenario: Scenario) -> Result<Self> {
         Ok(Self {
-            scenario,
+            scenario: Some(scenario),
+            tfhe_params: *scenario_to_tfhe_params(scenario),
+        })
+    }
+
+    /// Create parameters from a raw TFHE Boolean parameter set.
+    ///
+    /// This is meant for parameter sets tuned by cryptographers. The parameters
+    /// are checked for structural sanity, but
+    /// **not** for security or correctness: a badly chosen set yields insecure
+    /// keys or wrong results. Prefer [`Params::for_scenario`] unless you know
+    /// the impact of every parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameters fail validation.
+    pub fn custom(tfhe_params: BooleanParameters) -> Result<Self> {
+        validate_tfhe_params(&tfhe_params)?;
+        Ok(Self {
+            scenario: None,
+            tfhe_params,
+        })
+    }
+
+    /// Select the least costly built-in parameter set meeting both targets.
+    ///
+    /// Every built-in parameter set is compared against the requested
+    /// security level and maximum error probability per bootstrap. The returned
+    /// [`ParamsReport`] holds the selected parameters along with the
+    /// assessment of every candidate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no built-in parameter set meets both targets.
+    pub fn for_security(
+        security: SecurityLevel,
+        error_probability: ErrorProbability,
+    ) -> Result<ParamsReport> {
+        let mut candidates: Vec<CandidateAssessment> = BUILT_IN_PARAMETER_SETS
+            .iter()
+            .map(|&info| CandidateAssessment {
+                info,
+                meets_security: info.security_bits >= security.bits(),
+                meets_error_probability: info.error_probability_log2
+                    <= -f64::from(error_probability.exponent()),
+                selected: false,
+            })
+            .collect();
+        candidates.sort_by_key(|candidate| candidate.info.cost);
+
+        let Some(selected) = candidates.iter_mut().find(|candidate| candidate.meets_targets()) else {
+            anyhow::bail!(
+                "No built-in parameter set provides {}-bit security with an error probability of at most {}",
+                security.bits(),
+                error_probability
+            );
+        };
+        selected.selected = true;
+        let params = Self::for_scenario(selected.info.scenario)?;
+
+        Ok(ParamsReport {
+            params,
+            security,
+            error_probability,
+            candidates,
+        })
+    }
+
+    /// Recommend the least costly built-in parameter set for a circuit.
+    ///
+    /// The probability that an evaluation of `circuit` goes wrong is bounded
+    /// by its bootstrap count (see [`Circuit::bootstrap_count`]) times the
+    /// per-bootstrap failure probability of the parameter set. The cheapest
+    /// set meeting the security level whose bound stays under the target is
+    /// selected, and its evaluation time is estimated from the circuit's
+    /// [`complexity_estimate`](Circuit::complexity_estimate) scaled by the
+    /// cost of the set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no built-in parameter set meets the requirements.
+    pub fn recommend_for(circuit: &Circuit, requirements: Requirements) -> Result<Recommendation> {
+        let bootstraps = circuit.bootstrap_count();
+        // log2 of the union bound over all bootstraps; zero bootstraps never fail
+        let circuit_error_log2 = |info: &ParameterSetInfo| {
+            if bootstraps == 0 {
+                f64::NEG_INFINITY
+            } else {
+                info.error_probability_log2 + (bootstraps as f64).log2()
+            }
+        };
+
+        let target_log2 = -f64::from(requirements.circuit_error_probability.exponent());
+        let Some(info) = BUILT_IN_PARAMETER_SETS
+            .iter()
+            .filter(|info| info.security_bits >= requirements.security.bits())
+            .filter(|info| circuit_error_log2(info) <= target_log2)
+            .min_by_key(|info| info.cost)
+        else {
+            anyhow::bail!(
+                "No built-in parameter set keeps the error probability of {} bootstraps under {} with {}-bit security",
+                bootstraps,
+                requirements.circuit_error_probability,
+                requirements.security.bits()
+            );
+        };
+
+        // Timing estimates are calibrated for the default parameter set
+        let reference_cost = f64::from(scenario_info(Scenario::SafeAndBalanced).cost);
+        let estimated_evaluation_time_ms = circuit.complexity_estimate().estimated_evaluation_time_ms
+            * f64::from(info.cost)
+            / reference_cost;
+
+        Ok(Recommendation {
+            params: Self::for_scenario(info.scenario)?,
+            info: *info,
+            requirements,
+            bootstraps,
+            circuit_error_probability_log2: circuit_error_log2(info),
+            estimated_evaluation_time_ms,
         })
     }
 
+    /// Load parameters from a TOML document.
+    ///
+    /// The document either names a scenario or holds a custom parameter set:
+    ///
+    /// ```toml
+    /// scenario = "SafeAndBalanced"
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is malformed, the custom parameters
+    /// fail validation, or the `config` feature is not enabled.
+    pub fn from_toml_str(toml: &str) -> Result<Self> {
+        #[cfg(feature = "config")]
+        {
+            toml::from_str(toml).map_err(|e| anyhow::anyhow!("Invalid parameters configuration: {}", e))
+        }
+        #[cfg(not(feature = "config"))]
+        {
+            let _ = toml; // Suppress unused warning
+            anyhow::bail!("Loading parameters from TOML requires 'config' feature to be enabled")
+        }
+    }
+
+    /// Load parameters from a JSON document.
+    ///
+    /// The document has the same shape as for [`Params::from_toml_str`], e.g.
+    /// `{"scenario": "FastDemo"}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is malformed, the custom parameters
+    /// fail validation, or the `config` feature is not enabled.
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        #[cfg(feature = "config")]
+        {
+            serde_json::from_str(json).map_err(|e| anyhow::anyhow!("Invalid parameters configuration: {}", e))
+        }
+        #[cfg(not(feature = "config"))]
+        {
+            let _ = json; // Suppress unused warning
+            anyhow::bail!("Loading parameters from JSON requires 'config' feature to be enabled")
+        }
+    }
+
+    /// Compute the fingerprint of the TFHE parameter set.
+    ///
+    /// The fingerprint only depends on the TFHE parameters, so a custom set
+    /// equal to a scenario's has the same fingerprint as the scenario.
+    pub fn fingerprint(&self) -> ParamsFingerprint {
+        ParamsFingerprint::of(&self.tfhe_params)
+    }
+
     /// Get the scenario.
+    ///
+    /// Custom parameters have no scenario and report
+    /// [`Scenario::SafeAndBalanced`], the general-purpose default; check
+    /// [`Params::is_custom`] to tell them apart.
     pub fn scenario(&self) -> Scenario {
-        self.scenario
+        self.scenario.unwrap_or(Scenario::SafeAndBalanced)
+    }
+
+    /// Check if these parameters were created with [`Params::custom`].
+    pub fn is_custom(&self) -> bool {
+        self.scenario.is_none()
+    }
+
+    /// Get the raw TFHE parameters if these parameters were created with [`Params::custom`].
+    pub fn custom_parameters(&self) -> Option<&BooleanParameters> {
+        self.is_custom().then_some(&self.tfhe_params)
     }
 
     /// Check if Boolean operations are supported.
@@ -121,9 +399,482 @@ impl Params {
     }
 
     /// Get the TFHE Boolean parameters for key generation.
+    pub fn tfhe_boolean_params(&self) -> &BooleanParameters {
+        &self.tfhe_params
+    }
+}
+
+/// A SHA-256 fingerprint of a TFHE parameter set.
+///
+/// Keys and ciphertexts are only compatible with the parameters they were
+/// created with. Storing the fingerprint next to them makes a mismatch
+/// detectable before it turns into garbage results. It displays and parses as
+/// 64 lowercase hexadecimal digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParamsFingerprint([u8; 32]);
+
+impl ParamsFingerprint {
+    /// Compute the fingerprint of a TFHE parameter set.
+    pub fn of(params: &BooleanParameters) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"encircuit/params/v1");
+
+        // Fixed-width little-endian encoding of every field, in declaration order
+        for value in [params.lwe_dimension.0, params.glwe_dimension.0, params.polynomial_size.0] {
+            hasher.update((value as u64).to_le_bytes());
+        }
+        for distribution in [&params.lwe_noise_distribution, &params.glwe_noise_distribution] {
+            match distribution {
+                DynamicDistribution::Gaussian(gaussian) => {
+                    hasher.update([0]);
+                    hasher.update(gaussian.std.to_bits().to_le_bytes());
+                    hasher.update(gaussian.mean.to_bits().to_le_bytes());
+                }
+                DynamicDistribution::TUniform(uniform) => {
+                    hasher.update([1]);
+                    hasher.update(uniform.bound_log2().to_le_bytes());
+                }
+            }
+        }
+        for value in [
+            params.pbs_base_log.0,
+            params.pbs_level.0,
+            params.ks_base_log.0,
+            params.ks_level.0,
+        ] {
+            hasher.update((value as u64).to_le_bytes());
+        }
+        hasher.update([match params.encryption_key_choice {
+            EncryptionKeyChoice::Big => 0,
+            EncryptionKeyChoice::Small => 1,
+        }]);
+
+        Self(hasher.finalize().into())
+    }
+
+    /// Get the raw fingerprint bytes.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl fmt::Display for ParamsFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ParamsFingerprint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != 64 || !s.is_ascii() {
+            anyhow::bail!("Parameters fingerprint must be 64 hexadecimal digits");
+        }
+
+        let mut bytes = [0; 32];
+        for (byte, pair) in bytes.iter_mut().zip(s.as_bytes().chunks(2)) {
+            let pair = std::str::from_utf8(pair).expect("checked ASCII above");
+            *byte = u8::from_str_radix(pair, 16)
+                .map_err(|_| anyhow::anyhow!("Invalid hexadecimal digits in parameters fingerprint: {}", pair))?;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+/// Check a TFHE Boolean parameter set for structural sanity.
+///
+/// The checks catch parameter sets that cannot work at all, such as zero
+/// dimensions, polynomial sizes that are not powers of two, decompositions
+/// exceeding the 32-bit torus, or noise distributions outside the torus. They
+/// do not estimate security or failure probability.
+///
+/// # Errors
+///
+/// Returns an error describing the first failed check.
+fn validate_tfhe_params(params: &BooleanParameters) -> Result<()> {
+    if params.lwe_dimension.0 == 0 {
+        anyhow::bail!("LWE dimension must be greater than zero");
+    }
+    if params.glwe_dimension.0 == 0 {
+        anyhow::bail!("GLWE dimension must be greater than zero");
+    }
+
+    let polynomial_size = params.polynomial_size.0;
+    if polynomial_size < 2 || !polynomial_size.is_power_of_two() {
+        anyhow::bail!(
+            "Polynomial size must be a power of two greater than one, got {}",
+            polynomial_size
+        );
+    }
+
+    validate_decomposition("PBS", params.pbs_base_log.0, params.pbs_level.0)?;
+    validate_decomposition("Key switching", params.ks_base_log.0, params.ks_level.0)?;
+
+    validate_noise("LWE", &params.lwe_noise_distribution)?;
+    validate_noise("GLWE", &params.glwe_noise_distribution)?;
+
+    Ok(())
+}
+
+/// Check that a gadget decomposition fits in the 32-bit torus.
+fn validate_decomposition(name: &str, base_log: usize, level: usize) -> Result<()> {
+    if base_log == 0 || level == 0 {
+        anyhow::bail!(
+            "{} decomposition base log and level count must be greater than zero",
+            name
+        );
+    }
+    if base_log * level > u32::BITS as usize {
+        anyhow::bail!(
+            "{} decomposition uses {} bits (base log {} x level {}), more than the {}-bit torus",
+            name,
+            base_log * level,
+            base_log,
+            level,
+            u32::BITS
+        );
+    }
+    Ok(())
+}
+
+/// Check that a noise distribution is usable on the 32-bit torus.
+fn validate_noise(name: &str, distribution: &DynamicDistribution<u32>) -> Result<()> {
+    match distribution {
+        DynamicDistribution::Gaussian(gaussian) => {
+            // Standard deviations are expressed relative to the torus
+            if !gaussian.std.is_finite() || gaussian.std <= 0.0 || gaussian.std >= 1.0 {
+                anyhow::bail!(
+                    "{} noise standard deviation must be in (0, 1), got {}",
+                    name,
+                    gaussian.std
+                );
+            }
+        }
+        DynamicDistribution::TUniform(uniform) => {
+            if uniform.bound_log2() == 0 || uniform.bound_log2() > u32::BITS - 2 {
+                anyhow::bail!(
+                    "{} noise bound must be between 2^1 and 2^{}, got 2^{}",
+                    name,
+                    u32::BITS - 2,
+                    uniform.bound_log2()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A minimum security level, in bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SecurityLevel {
+    /// 128 bits of security, the standard target for production use.
+    Bits128,
+    /// 192 bits of security.
+    Bits192,
+    /// 256 bits of security.
+    Bits256,
+}
+
+impl SecurityLevel {
+    /// Get the security level in bits.
+    pub fn bits(self) -> u32 {
+        match self {
+            Self::Bits128 => 128,
+            Self::Bits192 => 192,
+            Self::Bits256 => 256,
+        }
+    }
+}
+
+/// A maximum failure probability of 2^-n.
+///
+/// Whether it bounds a single bootstrap or a whole circuit evaluation depends
+/// on where it is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ErrorProbability {
+    /// At most 2^-40.
+    Pow2Minus40,
+    /// At most 2^-64.
+    Pow2Minus64,
+    /// At most 2^-128.
+    Pow2Minus128,
+    /// At most 2^-165.
+    Pow2Minus165,
+}
+
+impl ErrorProbability {
+    /// Get `n` such that the probability is at most 2^-n.
+    pub fn exponent(self) -> u32 {
+        match self {
+            Self::Pow2Minus40 => 40,
+            Self::Pow2Minus64 => 64,
+            Self::Pow2Minus128 => 128,
+            Self::Pow2Minus165 => 165,
+        }
+    }
+}
+
+impl fmt::Display for ErrorProbability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "2^-{}", self.exponent())
+    }
+}
+
+/// Published characteristics of a built-in parameter set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParameterSetInfo {
+    /// The scenario using this parameter set.
+    pub scenario: Scenario,
+    /// Estimated security in bits.
+    pub security_bits: u32,
+    /// Base-2 logarithm of the probability that a bootstrap fails.
+    pub error_probability_log2: f64,
+    /// Relative cost of one bootstrap, as estimated by the parameter optimizer.
+    pub cost: u32,
+}
+
+impl fmt::Display for ParameterSetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} ({}-bit security, error probability 2^{:.1}, cost {})",
+            self.scenario, self.security_bits, self.error_probability_log2, self.cost
+        )
+    }
+}
+
+/// The built-in parameter sets, with the figures published alongside them in TFHE.
+const BUILT_IN_PARAMETER_SETS: [ParameterSetInfo; 4] = [
+    ParameterSetInfo {
+        scenario: Scenario::FastDemo,
+        security_bits: 128,
+        error_probability_log2: -64.017,
+        cost: 67,
+    },
+    ParameterSetInfo {
+        scenario: Scenario::SafeAndBalanced,
+        security_bits: 132,
+        error_probability_log2: -64.344,
+        cost: 75,
+    },
+    ParameterSetInfo {
+        scenario: Scenario::MaxSecurityMinimalOps,
+        security_bits: 128,
+        error_probability_log2: -166.826,
+        cost: 108,
+    },
+    ParameterSetInfo {
+        scenario: Scenario::DeepCircuitUltraLowError,
+        security_bits: 128,
+        error_probability_log2: -165.434,
+        cost: 117,
+    },
+];
+
+/// Look up the published characteristics of a scenario's parameter set.
+fn scenario_info(scenario: Scenario) -> &'static ParameterSetInfo {
+    BUILT_IN_PARAMETER_SETS
+        .iter()
+        .find(|info| info.scenario == scenario)
+        .expect("every scenario has a built-in parameter set")
+}
+
+/// How a built-in parameter set compares against the requested targets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandidateAssessment {
+    /// The parameter set.
+    pub info: ParameterSetInfo,
+    /// Whether the set reaches the requested security level.
+    pub meets_security: bool,
+    /// Whether the set's error probability is at most the requested one.
+    pub meets_error_probability: bool,
+    /// Whether the set was selected.
+    pub selected: bool,
+}
+
+impl CandidateAssessment {
+    /// Check whether the set meets both targets.
+    pub fn meets_targets(&self) -> bool {
+        self.meets_security && self.meets_error_probability
+    }
+}
+
+/// The outcome of [`Params::for_security`].
+///
+/// Its `Display` implementation explains the choice in plain text, one line
+/// per candidate.
+#[derive(Debug, Clone)]
+pub struct ParamsReport {
+    params: Params,
+    security: SecurityLevel,
+    error_probability: ErrorProbability,
+    candidates: Vec<CandidateAssessment>,
+}
+
+impl ParamsReport {
+    /// Get the selected parameters.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Take the selected parameters.
+    pub fn into_params(self) -> Params {
+        self.params
+    }
+
+    /// Get the requested security level.
+    pub fn requested_security(&self) -> SecurityLevel {
+        self.security
+    }
+
+    /// Get the requested maximum error probability per bootstrap.
+    pub fn requested_error_probability(&self) -> ErrorProbability {
+        self.error_probability
+    }
+
+    /// Get the characteristics of the selected parameter set.
+    pub fn selected(&self) -> &ParameterSetInfo {
+        &self
+            .candidates
+            .iter()
+            .find(|candidate| candidate.selected)
+            .expect("a report always has a selected candidate")
+            .info
+    }
+
+    /// Get the assessment of every built-in parameter set, cheapest first.
+    pub fn candidates(&self) -> &[CandidateAssessment] {
+        &self.candidates
+    }
+}
+
+impl fmt::Display for ParamsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Requested {}-bit security with an error probability of at most {}",
+            self.security.bits(),
+            self.error_probability
+        )?;
+        writeln!(f, "Selected {}", self.selected())?;
+        for candidate in &self.candidates {
+            let verdict = if candidate.selected {
+                "selected: least costly set meeting both targets"
+            } else if candidate.meets_targets() {
+                "meets both targets but costs more"
+            } else if !candidate.meets_security {
+                "rejected: security level too low"
+            } else {
+                "rejected: error probability too high"
+            };
+            writeln!(f, "  - {}: {}", candidate.info, verdict)?;
+        }
+        Ok(())
+    }
+}
+
+/// Requirements for [`Params::recommend_for`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Requirements {
+    /// Minimum security level.
+    pub security: SecurityLevel,
+    /// Maximum probability that an evaluation of the whole circuit goes wrong.
+    pub circuit_error_probability: ErrorProbability,
+}
+
+impl Requirements {
+    /// Create requirements from a security level and a whole-circuit error target.
+    pub fn new(security: SecurityLevel, circuit_error_probability: ErrorProbability) -> Self {
+        Self {
+            security,
+            circuit_error_probability,
+        }
+    }
+}
+
+impl Default for Requirements {
+    /// 128-bit security and a whole-circuit error probability of at most 2^-40.
+    fn default() -> Self {
+        Self::new(SecurityLevel::Bits128, ErrorProbability::Pow2Minus40)
+    }
+}
+
+/// The outcome of [`Params::recommend_for`].
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    params: Params,
+    info: ParameterSetInfo,
+    requirements: Requirements,
+    bootstraps: usize,
+    circuit_error_probability_log2: f64,
+    estimated_evaluation_time_ms: f64,
+}
+
+impl Recommendation {
+    /// Get the recommended parameters.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Take the recommended parameters.
+    pub fn into_params(self) -> Params {
+        self.params
+    }
+
+    /// Get the characteristics of the recommended parameter set.
+    pub fn selected(&self) -> &ParameterSetInfo {
+        &self.info
+    }
+
+    /// Get the requirements the recommendation was made for.
+    pub fn requirements(&self) -> Requirements {
+        self.requirements
+    }
+
+    /// Get the number of bootstraps the circuit performs.
+    pub fn bootstrap_count(&self) -> usize {
+        self.bootstraps
+    }
+
+    /// Get the base-2 logarithm of the bound on the whole-circuit error probability.
     ///
-    /// Uses the scenario to select appropriate TFHE parameters.
-    pub fn tfhe_boolean_params(&self) -> &'static BooleanParameters {
-        scenario_to_tfhe_params(self.scenario)
+    /// This is negative infinity for circuits without bootstraps.
+    pub fn circuit_error_probability_log2(&self) -> f64 {
+        self.circuit_error_probability_log2
+    }
+
+    /// Get the estimated evaluation time in milliseconds with the recommended parameters.
+    pub fn estimated_evaluation_time_ms(&self) -> f64 {
+        self.estimated_evaluation_time_ms
+    }
+}
+
+impl fmt::Display for Recommendation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Recommended {}", self.info)?;
+        if self.bootstraps == 0 {
+            writeln!(f, "  No bootstraps, so the circuit cannot fail")?;
+        } else {
+            writeln!(
+                f,
+                "  {} bootstraps, whole-circuit error probability at most 2^{:.1} (target {})",
+                self.bootstraps,
+                self.circuit_error_probability_log2,
+                self.requirements.circuit_error_probability
+            )?;
+        }
+        writeln!(
+            f,
+            "  Estimated evaluation time: {:.2} ms",
+            self.estimated_evaluation_time_ms
+        )
     }
 }
@@ -0,0 +1,135 @@
+/*!
+Passphrase-based encryption of secret key material at rest.
+
+Secrets are encrypted with ChaCha20-Poly1305 under a key derived from the
+passphrase with Argon2id, a memory-hard function that makes guessing
+passphrases expensive. Every container gets a fresh random salt and nonce.
+
+## Container Format
+
+| Bytes | Content                                           |
+|-------|---------------------------------------------------|
+| 4     | Magic `ENCK`                                      |
+| 1     | Format version, currently 1                       |
+| 12    | Argon2id memory (KiB), iterations and parallelism |
+| 16    | Salt                                              |
+| 12    | Nonce                                             |
+| rest  | Ciphertext and 16-byte authentication tag         |
+
+The header is authenticated along with the ciphertext, so tampering with any
+byte, including the key derivation parameters, is detected on decryption.
+*/
+
+use crate::secret::SecretBytes;
+use anyhow::Result;
+use argon2::{Algorithm, Argon2, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng, Payload, rand_core::RngCore};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use zeroize::Zeroizing;
+
+const MAGIC: &[u8; 4] = b"ENCK";
+const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KDF_OFFSET: usize = MAGIC.len() + 1;
+const SALT_OFFSET: usize = KDF_OFFSET + 3 * 4;
+const NONCE_OFFSET: usize = SALT_OFFSET + SALT_LEN;
+const HEADER_LEN: usize = NONCE_OFFSET + NONCE_LEN;
+const TAG_LEN: usize = 16;
+
+/// Argon2id memory cost in KiB, iterations and parallelism for new containers.
+const KDF_PARAMS: (u32, u32, u32) = (
+    argon2::Params::DEFAULT_M_COST,
+    argon2::Params::DEFAULT_T_COST,
+    argon2::Params::DEFAULT_P_COST,
+);
+
+/// Largest memory cost accepted when opening a container, 1 GiB.
+///
+/// Keeps a crafted header from making us allocate arbitrary amounts of memory.
+const MAX_M_COST: u32 = 1024 * 1024;
+/// Largest iteration count and parallelism accepted when opening a container.
+const MAX_T_COST: u32 = 64;
+const MAX_P_COST: u32 = 64;
+
+/// Encrypt `secret` under `passphrase` into a versioned container.
+///
+/// # Errors
+///
+/// Returns an error if the passphrase is empty.
+pub(crate) fn seal(secret: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    if passphrase.is_empty() {
+        anyhow::bail!("Passphrase must not be empty");
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let (m_cost, t_cost, p_cost) = KDF_PARAMS;
+    let mut container = Vec::with_capacity(HEADER_LEN + secret.len() + TAG_LEN);
+    container.extend_from_slice(MAGIC);
+    container.push(VERSION);
+    container.extend_from_slice(&m_cost.to_le_bytes());
+    container.extend_from_slice(&t_cost.to_le_bytes());
+    container.extend_from_slice(&p_cost.to_le_bytes());
+    container.extend_from_slice(&salt);
+    container.extend_from_slice(&nonce);
+
+    let cipher = derive_cipher(passphrase, &salt, KDF_PARAMS)?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: secret, aad: &container })
+        .map_err(|_| anyhow::anyhow!("Encryption failed"))?;
+    container.extend_from_slice(&ciphertext);
+    Ok(container)
+}
+
+/// Decrypt a container produced by [`seal`].
+///
+/// # Errors
+///
+/// Returns an error if the data is not a container, uses an unsupported
+/// version or key derivation parameters, or the passphrase is wrong or the
+/// container was modified.
+pub(crate) fn open(container: &[u8], passphrase: &str) -> Result<SecretBytes> {
+    if container.len() < HEADER_LEN + TAG_LEN || &container[..MAGIC.len()] != MAGIC {
+        anyhow::bail!("Data is not a passphrase-encrypted container");
+    }
+    let version = container[MAGIC.len()];
+    if version != VERSION {
+        anyhow::bail!("Unsupported container version {}, expected {}", version, VERSION);
+    }
+
+    let (header, ciphertext) = container.split_at(HEADER_LEN);
+    let read_u32 = |offset: usize| u32::from_le_bytes(header[offset..offset + 4].try_into().expect("4 bytes"));
+    let kdf_params = (read_u32(KDF_OFFSET), read_u32(KDF_OFFSET + 4), read_u32(KDF_OFFSET + 8));
+    let (m_cost, t_cost, p_cost) = kdf_params;
+    if m_cost > MAX_M_COST || t_cost > MAX_T_COST || p_cost > MAX_P_COST {
+        anyhow::bail!(
+            "Key derivation parameters exceed the supported limits: {} KiB, {} iterations, parallelism {}",
+            m_cost,
+            t_cost,
+            p_cost
+        );
+    }
+    let salt = &header[SALT_OFFSET..NONCE_OFFSET];
+    let nonce = &header[NONCE_OFFSET..];
+
+    let cipher = derive_cipher(passphrase, salt, kdf_params)?;
+    cipher
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: header })
+        .map(SecretBytes::new)
+        .map_err(|_| anyhow::anyhow!("Decryption failed: wrong passphrase or corrupted data"))
+}
+
+/// Derive the AEAD cipher from the passphrase with Argon2id.
+fn derive_cipher(passphrase: &str, salt: &[u8], (m_cost, t_cost, p_cost): (u32, u32, u32)) -> Result<ChaCha20Poly1305> {
+    let params = argon2::Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|e| anyhow::anyhow!("Invalid key derivation parameters: {}", e))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| anyhow::anyhow!("Key derivation failed: {}", e))?;
+    Ok(ChaCha20Poly1305::new(Key::from_slice(key.as_ref())))
+}
@@ -8,13 +8,14 @@ from the encircuit crate with a single `use encircuit::prelude::*;` statement.
 // Core types
 pub use crate::{
     BoolCt, Circuit, CircuitBuilder, CircuitComplexity, ClientKeyBytes, EncryptedCircuit, Keyset, Params,
-    ServerKeyBytes,
+    PublicKeyBytes, ServerKeyBytes,
 };
 
 // Parameter configuration
-pub use crate::params::Scenario;
+pub use crate::params::{ErrorProbability, Requirements, Scenario, SecurityLevel};
 
 // Core traits
+pub use crate::backend::Backend;
 pub use crate::ciphertext::{Decryptable, Encryptable};
 
 // Re-export common error types
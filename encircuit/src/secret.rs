@@ -0,0 +1,76 @@
+/*!
+Containers for secret key material that are wiped from memory when dropped.
+
+Serialized client keys are returned as [`SecretBytes`] rather than a plain
+`Vec<u8>`, so that the buffer is overwritten with zeros once it is no longer
+needed, instead of lingering in freed memory.
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, _) = Keyset::generate(&params)?.split();
+let bytes = client_key.as_bytes()?;
+std::fs::write("client.key", &*bytes)?;
+// `bytes` is wiped here
+# Ok(())
+# }
+```
+*/
+
+use std::fmt;
+use std::ops::Deref;
+use zeroize::Zeroizing;
+
+/// Bytes holding secret key material, zeroized on drop.
+///
+/// Derefs to `[u8]`, so it can be written out or handed to
+/// [`ClientKeyBytes::from_bytes`](crate::ClientKeyBytes::from_bytes) without
+/// copying. Copies taken out of it, e.g. with `to_vec`, are not wiped.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretBytes(Zeroizing<Vec<u8>>);
+
+impl SecretBytes {
+    /// Wrap a buffer, taking over the responsibility of wiping it.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(Zeroizing::new(bytes))
+    }
+
+    /// Get the buffer to write secret bytes into.
+    ///
+    /// Pushing past the capacity reallocates and leaves the old allocation unwiped.
+    pub(crate) fn buffer(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl Deref for SecretBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for SecretBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretBytes")
+            .field("len", &self.0.len())
+            .field("bytes", &"<redacted>")
+            .finish()
+    }
+}
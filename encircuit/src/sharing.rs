@@ -0,0 +1,355 @@
+/*!
+Threshold secret sharing of client keys.
+
+[`ClientKeyBytes::split_into_shares`] splits a serialized client key into `n`
+[`KeyShare`]s with Shamir's scheme over GF(256), so that any `t` of them
+reconstruct the key with [`ClientKeyBytes::from_shares`] while fewer than `t`
+reveal nothing about it. Each share can be handed to a different operator.
+
+Shares are versioned and authenticated in two ways:
+
+- Every share carries a tag over its own content, which detects corruption.
+- Every share also commits to the content of all shares of the same split,
+  so a share forged or mixed in from another split is rejected as long as
+  the other shares are genuine.
+
+Reconstruction errors name the offending share by its position in the input
+and its share index.
+
+## Example
+
+```rust,no_run
+use encircuit::prelude::*;
+use encircuit::KeyShare;
+# fn main() -> anyhow::Result<()> {
+# let params = Params::for_scenario(Scenario::FastDemo)?;
+# let (client_key, _) = Keyset::generate(&params)?.split();
+// Any 3 of 5 operators can restore the key
+let shares = client_key.split_into_shares(3, 5)?;
+let stored: Vec<Vec<u8>> = shares.iter().map(KeyShare::to_bytes).collect::<Result<_>>()?;
+
+let quorum: Vec<KeyShare> = [&stored[0], &stored[2], &stored[4]]
+    .into_iter()
+    .map(|bytes| KeyShare::from_bytes(bytes))
+    .collect::<Result<_>>()?;
+let restored = ClientKeyBytes::from_shares(&quorum)?;
+assert_eq!(restored.id(), client_key.id());
+# Ok(())
+# }
+```
+*/
+
+use crate::keys::KeyId;
+use crate::secret::SecretBytes;
+use anyhow::Result;
+use chacha20poly1305::aead::{OsRng, rand_core::RngCore};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use zeroize::{Zeroize, Zeroizing};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const VERSION: u8 = 1;
+
+/// One share of a client key split with [`ClientKeyBytes::split_into_shares`](crate::ClientKeyBytes::split_into_shares).
+///
+/// The share content is zeroized on drop.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KeyShare {
+    version: u8,
+    key_id: KeyId,
+    /// Random id shared by all shares of one split.
+    split_id: [u8; 16],
+    threshold: u8,
+    /// The evaluation point of the share, from 1 to the number of shares.
+    index: u8,
+    /// Digest of the payload of every share of the split, by index.
+    commitments: Vec<[u8; 32]>,
+    payload: Vec<u8>,
+    tag: [u8; 32],
+}
+
+impl KeyShare {
+    /// Get the index of this share, from 1 to the number of shares.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Get the number of shares needed to reconstruct the key.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Get the number of shares the key was split into.
+    pub fn share_count(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// Get the id of the keyset the shared client key belongs to.
+    pub fn key_id(&self) -> KeyId {
+        self.key_id
+    }
+
+    /// Create a share from bytes produced by [`KeyShare::to_bytes`].
+    ///
+    /// The share is only checked when reconstructing the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `serde` feature is not enabled or the data is invalid.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::deserialize(data)
+                .map_err(|e| anyhow::anyhow!("Key share deserialization failed: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let _ = data; // Suppress unused warning
+            anyhow::bail!("Deserialization requires 'serde' feature to be enabled")
+        }
+    }
+
+    /// Serialize the share to bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `serde` feature is not enabled or serialization fails.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        #[cfg(feature = "serde")]
+        {
+            bincode::serialize(self)
+                .map_err(|e| anyhow::anyhow!("Key share serialization failed: {}", e))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            anyhow::bail!("Serialization requires 'serde' feature to be enabled")
+        }
+    }
+
+    /// Compute the tag over everything but the tag itself.
+    fn compute_tag(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"encircuit/key-share/v1");
+        hasher.update([self.version]);
+        hasher.update(self.key_id.as_bytes());
+        hasher.update(self.split_id);
+        hasher.update([self.threshold, self.index]);
+        hasher.update((self.commitments.len() as u64).to_le_bytes());
+        for commitment in &self.commitments {
+            hasher.update(commitment);
+        }
+        hasher.update(&self.payload);
+        hasher.finalize().into()
+    }
+
+    /// Alter the payload and update the share's own commitment and tag, as a
+    /// forger holding the share could.
+    #[cfg(all(test, feature = "serde"))]
+    pub(crate) fn forged(&self) -> Self {
+        let mut forged = self.clone();
+        forged.payload[0] ^= 1;
+        forged.commitments[usize::from(forged.index) - 1] = commitment(forged.index, &forged.payload);
+        forged.tag = forged.compute_tag();
+        forged
+    }
+
+    /// Describe the share for error messages, e.g. `Share 2 (index 5)`.
+    fn describe(&self, position: usize) -> String {
+        format!("Share {} (index {})", position + 1, self.index)
+    }
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        self.payload.zeroize();
+    }
+}
+
+impl fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyShare")
+            .field("key_id", &format_args!("{}", self.key_id))
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("share_count", &self.commitments.len())
+            .field("payload", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Split `secret` into `count` shares, any `threshold` of which reconstruct it.
+pub(crate) fn split(secret: &[u8], key_id: KeyId, threshold: u8, count: u8) -> Result<Vec<KeyShare>> {
+    if threshold < 2 {
+        anyhow::bail!("Threshold must be at least 2, otherwise every share contains the whole key");
+    }
+    if count < threshold {
+        anyhow::bail!("Cannot split into {} shares with threshold {}", count, threshold);
+    }
+
+    let mut split_id = [0u8; 16];
+    OsRng.fill_bytes(&mut split_id);
+
+    // Random coefficients 1..threshold of the polynomial of every byte
+    let degree = usize::from(threshold - 1);
+    let mut coefficients = Zeroizing::new(vec![0u8; secret.len() * degree]);
+    OsRng.fill_bytes(&mut coefficients);
+
+    let payloads: Vec<(u8, Vec<u8>)> = (1..=count)
+        .map(|x| {
+            let payload = secret
+                .iter()
+                .zip(coefficients.chunks_exact(degree))
+                .map(|(&constant, coefficients)| {
+                    // Horner's rule from the highest coefficient down to the secret byte
+                    let higher = coefficients.iter().rev().fold(0, |acc, &c| gf_mul(acc, x) ^ c);
+                    gf_mul(higher, x) ^ constant
+                })
+                .collect();
+            (x, payload)
+        })
+        .collect();
+    let commitments: Vec<[u8; 32]> = payloads
+        .iter()
+        .map(|(index, payload)| commitment(*index, payload))
+        .collect();
+
+    Ok(payloads
+        .into_iter()
+        .map(|(index, payload)| {
+            let mut share = KeyShare {
+                version: VERSION,
+                key_id,
+                split_id,
+                threshold,
+                index,
+                commitments: commitments.clone(),
+                payload,
+                tag: [0; 32],
+            };
+            share.tag = share.compute_tag();
+            share
+        })
+        .collect())
+}
+
+/// Check the shares and reconstruct the secret they share.
+///
+/// Returns the key id recorded in the shares along with the secret.
+pub(crate) fn combine(shares: &[KeyShare]) -> Result<(KeyId, SecretBytes)> {
+    let Some(first) = shares.first() else {
+        anyhow::bail!("No key shares provided");
+    };
+
+    for (position, share) in shares.iter().enumerate() {
+        if share.version != VERSION {
+            anyhow::bail!(
+                "{} uses unsupported version {}, expected {}",
+                share.describe(position),
+                share.version,
+                VERSION
+            );
+        }
+        if share.tag != share.compute_tag() {
+            anyhow::bail!("{} is corrupted: authentication tag mismatch", share.describe(position));
+        }
+        if share.index == 0 || usize::from(share.index) > share.commitments.len() {
+            anyhow::bail!("{} has an index outside its split", share.describe(position));
+        }
+        if share.split_id != first.split_id
+            || share.key_id != first.key_id
+            || share.threshold != first.threshold
+            || share.commitments.len() != first.commitments.len()
+        {
+            anyhow::bail!("{} belongs to another split than share 1", share.describe(position));
+        }
+        if shares[..position].iter().any(|other| other.index == share.index) {
+            anyhow::bail!("{} is a duplicate", share.describe(position));
+        }
+    }
+
+    // Every share must match the commitments of all other shares; blame the
+    // share the fewest others vouch for
+    let vouchers = |share: &KeyShare| {
+        let digest = commitment(share.index, &share.payload);
+        shares
+            .iter()
+            .filter(|other| other.index != share.index)
+            .filter(|other| other.commitments[usize::from(share.index) - 1] == digest)
+            .count()
+    };
+    if let Some((position, share)) = shares
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, share)| vouchers(share))
+        .filter(|(_, share)| vouchers(share) < shares.len() - 1)
+    {
+        anyhow::bail!(
+            "{} doesn't match the commitments of the other shares",
+            share.describe(position)
+        );
+    }
+
+    let threshold = usize::from(first.threshold);
+    if shares.len() < threshold {
+        anyhow::bail!("Need at least {} key shares, got {}", threshold, shares.len());
+    }
+
+    // Lagrange interpolation at zero over the first `threshold` shares
+    let quorum = &shares[..threshold];
+    let mut secret = SecretBytes::new(vec![0u8; first.payload.len()]);
+    let buffer = secret.buffer();
+    for share in quorum {
+        if share.payload.len() != buffer.len() {
+            anyhow::bail!("Key shares have different lengths");
+        }
+        let basis = quorum
+            .iter()
+            .filter(|other| other.index != share.index)
+            .fold(1, |acc, other| gf_mul(acc, gf_div(other.index, other.index ^ share.index)));
+        for (byte, &y) in buffer.iter_mut().zip(&share.payload) {
+            *byte ^= gf_mul(y, basis);
+        }
+    }
+
+    Ok((first.key_id, secret))
+}
+
+/// Digest of the payload of the share with the given index.
+fn commitment(index: u8, payload: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"encircuit/key-share-payload/v1");
+    hasher.update([index]);
+    hasher.update(payload);
+    hasher.finalize().into()
+}
+
+/// Multiply in GF(2^8) modulo the AES polynomial x^8 + x^4 + x^3 + x + 1.
+///
+/// Runs the same operations for every input, so timing doesn't depend on secrets.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Divide in GF(2^8); `b` must not be zero.
+fn gf_div(a: u8, b: u8) -> u8 {
+    // b^254 is the inverse of b, since b^255 = 1
+    let mut inverse = 1;
+    let mut power = b;
+    for bit in 0..8 {
+        if (254 >> bit) & 1 == 1 {
+            inverse = gf_mul(inverse, power);
+        }
+        power = gf_mul(power, power);
+    }
+    gf_mul(a, inverse)
+}
@@ -0,0 +1,123 @@
+/*!
+Tests for assembling circuit inputs from several contributors.
+*/
+
+use crate::prelude::*;
+use crate::{InputAssembly, InputContribution};
+use super::fixtures::TestFixture;
+
+/// Build `(a AND b) XOR c` with labelled inputs `a` and `c`; `b` stays unlabelled.
+fn labelled_circuit() -> Circuit {
+    let mut builder = CircuitBuilder::default();
+    let a = builder.input();
+    let b = builder.input();
+    let c = builder.input();
+    builder.label(a, "a");
+    builder.label(c, "c");
+    let and = builder.and(a, b);
+    let out = builder.xor(and, c);
+    builder.finish(out)
+}
+
+#[test]
+fn test_assembly_merges_contributions() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = labelled_circuit();
+    let encrypt = |value: bool| value.encrypt(&fixture.client_key).expect("Failed to encrypt");
+
+    let mut first = InputContribution::new("first");
+    first.insert("a", encrypt(true)).insert(1, encrypt(true));
+    let mut second = InputContribution::new("second");
+    second.insert("c", encrypt(true));
+
+    // Contributions travel as bytes
+    #[cfg(feature = "serde")]
+    let second = InputContribution::from_bytes(second.to_bytes().expect("Failed to serialize"))
+        .expect("Failed to deserialize");
+    assert_eq!(second.contributor(), "second");
+
+    let mut assembly = InputAssembly::new(&circuit);
+    assembly.add(first).expect("Failed to add first contribution");
+    assert_eq!(assembly.missing_inputs(), vec![2]);
+    assembly.add(second).expect("Failed to add second contribution");
+    assert!(assembly.is_complete());
+
+    let encrypted = assembly.finish().expect("Failed to finish assembly");
+    let result = encrypted.evaluate(&fixture.server_key);
+    assert!(!result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
+
+#[test]
+fn test_assembly_rejects_invalid_contributions() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = labelled_circuit();
+    let ct = true.encrypt(&fixture.client_key).expect("Failed to encrypt");
+
+    let mut assembly = InputAssembly::new(&circuit);
+    let mut owner = InputContribution::new("owner");
+    owner.insert("a", ct.clone());
+    assembly.add(owner).expect("Failed to add contribution");
+
+    let cases: Vec<(InputContribution, &str)> = vec![
+        {
+            let mut c = InputContribution::new("typo");
+            c.insert("d", ct.clone());
+            (c, "no input labelled 'd'")
+        },
+        {
+            let mut c = InputContribution::new("range");
+            c.insert(3, ct.clone());
+            (c, "input 3 is out of range")
+        },
+        {
+            // A name and an index resolving to the same input
+            let mut c = InputContribution::new("twice");
+            c.insert("c", ct.clone()).insert(2, ct.clone());
+            (c, "provides input 2 ('c') more than once")
+        },
+        {
+            let mut c = InputContribution::new("intruder");
+            c.insert(1, ct.clone()).insert(0, ct.clone());
+            (c, "overlaps with 'owner' on input 0 ('a')")
+        },
+    ];
+    for (contribution, expected) in cases {
+        let err = assembly.add(contribution).expect_err("Invalid contribution was accepted");
+        assert!(err.to_string().contains(expected), "Expected {:?}, got: {}", expected, err);
+    }
+
+    // Rejected contributions are not partially merged
+    assert_eq!(assembly.missing_inputs(), vec![1, 2]);
+    let err = assembly.finish().expect_err("Incomplete assembly finished");
+    assert!(err.to_string().contains("Missing inputs: input 1, input 2 ('c')"), "{}", err);
+}
+
+#[test]
+fn test_assembly_rejects_ambiguous_labels() {
+    let fixture = TestFixture::fast_demo();
+    let ct = true.encrypt(&fixture.client_key).expect("Failed to encrypt");
+
+    // Labels also group gates for profiling, so two inputs may share one
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let y = builder.input();
+    let z = builder.input();
+    builder.label(x, "price");
+    builder.label(z, "price");
+    let and = builder.and(x, y);
+    let out = builder.or(and, z);
+    let circuit = builder.finish(out);
+
+    let mut assembly = InputAssembly::new(&circuit);
+    let mut ambiguous = InputContribution::new("seller");
+    ambiguous.insert("price", ct.clone());
+    let error = assembly.add(ambiguous).unwrap_err().to_string();
+    assert!(error.contains("'price' is ambiguous, it names inputs 0, 2"), "{}", error);
+    assert_eq!(assembly.missing_inputs(), vec![0, 1, 2]);
+
+    // Addressing the inputs by index still works
+    let mut by_index = InputContribution::new("seller");
+    by_index.insert(0, ct.clone()).insert(2, ct);
+    assembly.add(by_index).expect("Failed to add contribution by index");
+    assert_eq!(assembly.missing_inputs(), vec![1]);
+}
@@ -0,0 +1,47 @@
+/*!
+Tests for the async evaluation API.
+*/
+
+use crate::prelude::*;
+use crate::CancellationToken;
+use super::fixtures::{TestFixture, utils};
+use std::sync::Arc;
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("Failed to build runtime")
+}
+
+#[test]
+fn test_evaluate_async() {
+    let fixture = TestFixture::fast_demo();
+    let server_key = Arc::new(fixture.server_key.clone());
+    let encrypted = utils::xor_circuit()
+        .encrypt_inputs(&[true, false], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    let result = runtime()
+        .block_on(encrypted.evaluate_async(server_key))
+        .expect("Async evaluation failed");
+    assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
+
+#[test]
+fn test_evaluate_async_cancellation() {
+    let fixture = TestFixture::fast_demo();
+    let server_key = Arc::new(fixture.server_key.clone());
+    let encrypted = utils::xor_circuit()
+        .encrypt_inputs(&[true, false], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    // Dropping the future cancels the evaluation
+    let token = CancellationToken::new();
+    let future = encrypted.evaluate_async_with(server_key.clone(), token.clone(), |_| {});
+    drop(future);
+    assert!(token.is_cancelled());
+
+    // A cancelled token makes the future resolve to an error
+    let result = runtime().block_on(encrypted.evaluate_async_with(server_key, token, |_| {}));
+    assert!(result.unwrap_err().to_string().contains("cancelled"));
+}
@@ -0,0 +1,170 @@
+/*!
+Tests for the pluggable evaluation backends.
+*/
+
+use crate::prelude::*;
+use crate::{MockBackend, MockCiphertext, PlaintextBackend, TfheBackend};
+use super::fixtures::{TestFixture, utils};
+use proptest::prelude::*;
+
+/// Build `(x AND NOT y) XOR (z OR true)`, which uses every gate type.
+fn all_gates_circuit() -> Circuit {
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let y = builder.input();
+    let z = builder.input();
+    let not_y = builder.not(y);
+    let and = builder.and(x, not_y);
+    let constant = builder.constant(true);
+    let or = builder.or(z, constant);
+    let output = builder.xor(and, or);
+    builder.finish(output)
+}
+
+#[test]
+fn test_plaintext_backend_truth_tables() {
+    let circuit = all_gates_circuit();
+
+    for bits in 0..8u8 {
+        let inputs = [bits & 1 != 0, bits & 2 != 0, bits & 4 != 0];
+        // (z OR true) is always true, so the XOR negates the AND
+        let expected = !inputs[0] || inputs[1];
+        let result = circuit
+            .evaluate_with(&PlaintextBackend, &(), &inputs)
+            .expect("Plaintext evaluation failed");
+        assert_eq!(result, vec![expected], "inputs {:?}", inputs);
+    }
+}
+
+#[test]
+fn test_mock_backend_counts_gates() {
+    let circuit = all_gates_circuit();
+    let backend = MockBackend::new();
+
+    let inputs = [true, false, false].map(MockCiphertext::encrypt);
+    let result = circuit
+        .evaluate_with(&backend, &(), &inputs)
+        .expect("Mock evaluation failed");
+
+    // (1 & !0) ^ (0 | 1) = false
+    assert!(!result[0].decrypt());
+
+    // `z OR true` folds to true and `XOR true` becomes a NOT, leaving one bootstrap
+    assert_eq!(backend.bootstrap_count(), 1);
+    assert_eq!(backend.not_count(), 2);
+    assert_eq!(backend.constant_count(), 0);
+
+    backend.reset_counts();
+    assert_eq!(backend.bootstrap_count(), 0);
+}
+
+#[test]
+fn test_constant_folding() {
+    let backend = MockBackend::new();
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let t = builder.constant(true);
+    let f = builder.constant(false);
+    let and_true = builder.and(x, t); // x
+    let or_false = builder.or(and_true, f); // x
+    let xor_false = builder.xor(f, or_false); // x
+    let and_false = builder.and(xor_false, f); // false
+    let output = builder.or(and_false, xor_false); // x
+    let circuit = builder.finish(output);
+
+    for value in [false, true] {
+        let result = circuit
+            .evaluate_with(&backend, &(), &[MockCiphertext::encrypt(value)])
+            .expect("Mock evaluation failed");
+        assert_eq!(result[0].decrypt(), value);
+    }
+    assert_eq!(backend.bootstrap_count(), 0);
+    assert_eq!(backend.not_count(), 0);
+    assert_eq!(backend.constant_count(), 0);
+}
+
+#[test]
+fn test_constant_output_is_materialized() {
+    let backend = MockBackend::new();
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let f = builder.constant(false);
+    let and = builder.and(x, f);
+    let output = builder.not(and);
+    let circuit = builder.finish(output);
+
+    let result = circuit
+        .evaluate_with(&backend, &(), &[MockCiphertext::encrypt(false)])
+        .expect("Mock evaluation failed");
+    assert!(result[0].decrypt());
+    assert_eq!(backend.constant_count(), 1);
+    assert_eq!(backend.bootstrap_count(), 0);
+}
+
+#[test]
+fn test_backend_input_count_mismatch() {
+    let circuit = utils::and_circuit();
+    let result = circuit.evaluate_with(&PlaintextBackend, &(), &[true]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_tfhe_backend_matches_plaintext() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = utils::xor_circuit();
+    let server_key = fixture.server_key.tfhe_key().expect("Failed to get TFHE key");
+
+    let inputs = [true, false];
+    let encrypted: Vec<BoolCt> = inputs
+        .iter()
+        .map(|bit| bit.encrypt(&fixture.client_key).expect("Failed to encrypt"))
+        .collect();
+
+    let result = circuit
+        .evaluate_with(&TfheBackend, server_key, &encrypted)
+        .expect("TFHE evaluation failed");
+    let expected = circuit
+        .evaluate_with(&PlaintextBackend, &(), &inputs)
+        .expect("Plaintext evaluation failed");
+
+    assert_eq!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"), expected[0]);
+}
+
+/// Build a random circuit from a list of gate descriptions.
+///
+/// Each entry selects a gate type and two operands among the nodes built so far.
+fn random_circuit(inputs: usize, gates: &[(u8, usize, usize)]) -> Circuit {
+    let mut builder = CircuitBuilder::default();
+    let mut nodes: Vec<_> = (0..inputs).map(|_| builder.input()).collect();
+
+    for &(kind, a, b) in gates {
+        let left = nodes[a % nodes.len()];
+        let right = nodes[b % nodes.len()];
+        let node = match kind % 5 {
+            0 => builder.and(left, right),
+            1 => builder.or(left, right),
+            2 => builder.xor(left, right),
+            3 => builder.not(left),
+            _ => builder.constant(a % 2 == 0),
+        };
+        nodes.push(node);
+    }
+
+    builder.finish(*nodes.last().expect("Circuit has no nodes"))
+}
+
+proptest! {
+    #[test]
+    fn prop_mock_backend_agrees_with_plaintext(
+        inputs in prop::collection::vec(any::<bool>(), 1..6),
+        gates in prop::collection::vec((any::<u8>(), any::<usize>(), any::<usize>()), 0..40),
+    ) {
+        let circuit = random_circuit(inputs.len(), &gates);
+        let expected = circuit.evaluate_with(&PlaintextBackend, &(), &inputs).unwrap();
+
+        let mock_inputs: Vec<_> = inputs.iter().copied().map(MockCiphertext::encrypt).collect();
+        let result = circuit.evaluate_with(&MockBackend::new(), &(), &mock_inputs).unwrap();
+
+        prop_assert_eq!(result[0].decrypt(), expected[0]);
+    }
+}
@@ -0,0 +1,105 @@
+/*!
+Tests for the on-disk keyset cache.
+*/
+
+use crate::prelude::*;
+use crate::KeyCache;
+use super::fixtures::TestFixture;
+
+/// A cache in a fresh directory, unique to the test and process.
+fn temp_cache(name: &str) -> KeyCache {
+    let dir = std::env::temp_dir().join(format!("encircuit-test-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    KeyCache::new(dir)
+}
+
+#[test]
+fn test_key_cache_round_trip() {
+    let fixture = TestFixture::fast_demo();
+    let params = Params::for_scenario(Scenario::FastDemo).unwrap();
+    let cache = temp_cache("round-trip");
+
+    assert!(cache.load(&params).expect("Failed to load").is_none());
+
+    let path = cache.store(&params, &fixture.keyset).expect("Failed to store keyset");
+    assert_eq!(path, cache.path_for(&params));
+    assert!(path.file_name().unwrap().to_string_lossy().starts_with(&params.fingerprint().to_string()));
+
+    // A cached keyset is loaded instead of generating a new one
+    let keyset = cache.get_or_generate(&params).expect("Failed to load keyset");
+    assert_eq!(keyset.client_key().id(), fixture.client_key.id());
+    assert_eq!(keyset.server_key().id(), fixture.server_key.id());
+
+    cache.clear().expect("Failed to clear cache");
+    assert!(cache.load(&params).expect("Failed to load").is_none());
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
+
+#[test]
+fn test_key_cache_integrity_checks() {
+    let fixture = TestFixture::fast_demo();
+    let params = Params::for_scenario(Scenario::FastDemo).unwrap();
+    let other_params = Params::for_scenario(Scenario::SafeAndBalanced).unwrap();
+    let cache = temp_cache("integrity");
+
+    let path = cache.store(&params, &fixture.keyset).expect("Failed to store keyset");
+    let data = std::fs::read(&path).unwrap();
+
+    // Flip a byte inside the client key
+    let mut corrupted = data.clone();
+    corrupted[100] ^= 1;
+    std::fs::write(&path, &corrupted).unwrap();
+    let error = cache.load(&params).unwrap_err().to_string();
+    assert!(error.contains("checksum mismatch"), "{}", error);
+
+    // Truncated by an interrupted copy
+    std::fs::write(&path, &data[..data.len() / 2]).unwrap();
+    assert!(cache.load(&params).is_err());
+
+    // A keyset filed under the wrong parameters
+    std::fs::write(cache.path_for(&other_params), &data).unwrap();
+    let error = cache.load(&other_params).unwrap_err().to_string();
+    assert!(error.contains("generated for other parameters"), "{}", error);
+
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_key_cache_is_private_to_the_user() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let fixture = TestFixture::fast_demo();
+    let params = Params::for_scenario(Scenario::FastDemo).unwrap();
+    let cache = temp_cache("private");
+
+    let path = cache.store(&params, &fixture.keyset).expect("Failed to store keyset");
+    let mode = |path: &std::path::Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode(cache.dir()), 0o700);
+    assert_eq!(mode(&path), 0o600);
+
+    // A directory other users can read is refused for loading and storing
+    std::fs::set_permissions(cache.dir(), std::fs::Permissions::from_mode(0o755)).unwrap();
+    let error = cache.load(&params).unwrap_err().to_string();
+    assert!(error.contains("accessible by other users"), "{}", error);
+    assert!(cache.store(&params, &fixture.keyset).is_err());
+    std::fs::set_permissions(cache.dir(), std::fs::Permissions::from_mode(0o700)).unwrap();
+
+    // So is a symbolic link, which could point anywhere
+    let link = cache.dir().with_extension("link");
+    let _ = std::fs::remove_file(&link);
+    std::os::unix::fs::symlink(cache.dir(), &link).unwrap();
+    let error = KeyCache::new(&link).load(&params).unwrap_err().to_string();
+    assert!(error.contains("symbolic link"), "{}", error);
+
+    std::fs::remove_file(&link).unwrap();
+    std::fs::remove_dir_all(cache.dir()).unwrap();
+}
+
+#[test]
+fn test_key_cache_in_user_cache_dir() {
+    if let Ok(cache) = KeyCache::in_user_cache_dir() {
+        assert!(cache.dir().is_absolute());
+        assert!(cache.dir().ends_with("encircuit/keys"));
+    }
+}
@@ -0,0 +1,177 @@
+/*!
+Tests for evaluation checkpoints and resuming interrupted evaluations.
+*/
+
+use crate::prelude::*;
+#[cfg(feature = "serde")]
+use crate::EvaluationCheckpoint;
+use super::fixtures::TestFixture;
+#[cfg(feature = "serde")]
+use super::fixtures::utils;
+
+/// Build `((x AND y) XOR NOT z) OR (x AND z)`, which has several live values mid-evaluation.
+fn mixed_circuit() -> Circuit {
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let y = builder.input();
+    let z = builder.input();
+    let and_xy = builder.and(x, y);
+    let not_z = builder.not(z);
+    let xor = builder.xor(and_xy, not_z);
+    let and_xz = builder.and(x, z);
+    let output = builder.or(xor, and_xz);
+    builder.finish(output)
+}
+
+#[test]
+fn test_checkpoints_are_emitted_periodically() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = mixed_circuit();
+
+    let encrypted = circuit
+        .encrypt_inputs(&[true, true, true], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    let mut checkpoints = Vec::new();
+    let result = encrypted
+        .try_evaluate_with_checkpoints(&fixture.server_key, 2, |checkpoint| {
+            checkpoints.push(checkpoint.clone());
+            Ok(())
+        })
+        .expect("Evaluation with checkpoints failed");
+
+    // 8 gates with an interval of 2 -> checkpoints after gates 2, 4 and 6
+    let positions: Vec<usize> = checkpoints.iter().map(|c| c.next_gate()).collect();
+    assert_eq!(positions, vec![2, 4, 6]);
+    assert!(checkpoints.iter().all(|c| c.validate().is_ok()));
+
+    // (1 & 1) ^ !1 | (1 & 1) = true
+    assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
+
+#[test]
+fn test_resume_from_every_checkpoint() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = mixed_circuit();
+
+    let inputs = [true, false, false];
+    let expected = ((inputs[0] && inputs[1]) ^ !inputs[2]) || (inputs[0] && inputs[2]);
+    let encrypted = circuit
+        .encrypt_inputs(&inputs, &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    let mut checkpoints = Vec::new();
+    encrypted
+        .try_evaluate_with_checkpoints(&fixture.server_key, 2, |checkpoint| {
+            checkpoints.push(checkpoint.clone());
+            Ok(())
+        })
+        .expect("Evaluation with checkpoints failed");
+
+    for checkpoint in checkpoints {
+        let next_gate = checkpoint.next_gate();
+        let result = EncryptedCircuit::resume_evaluation(checkpoint, &fixture.server_key)
+            .expect("Failed to resume evaluation");
+        let decrypted = result[0].decrypt(&fixture.client_key).expect("Failed to decrypt");
+        assert_eq!(decrypted, expected, "Resuming at gate {}", next_gate);
+    }
+}
+
+#[test]
+fn test_checkpoint_only_keeps_live_values() {
+    let fixture = TestFixture::fast_demo();
+
+    // A chain of NOT gates only ever needs the most recent value
+    let mut builder = CircuitBuilder::default();
+    let mut node = builder.input();
+    for _ in 0..5 {
+        node = builder.not(node);
+    }
+    let circuit = builder.finish(node);
+
+    let encrypted = circuit
+        .encrypt_inputs(&[false], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    let mut live_counts = Vec::new();
+    encrypted
+        .try_evaluate_with_checkpoints(&fixture.server_key, 1, |checkpoint| {
+            live_counts.push(checkpoint.live_value_count());
+            Ok(())
+        })
+        .expect("Evaluation with checkpoints failed");
+
+    assert!(live_counts.iter().all(|&count| count == 1), "{:?}", live_counts);
+}
+
+#[test]
+fn test_checkpoint_error_cases() {
+    let fixture = TestFixture::fast_demo();
+    let encrypted = mixed_circuit()
+        .encrypt_inputs(&[true, false, true], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    // A zero interval is rejected
+    let result = encrypted.try_evaluate_with_checkpoints(&fixture.server_key, 0, |_| Ok(()));
+    assert!(result.is_err());
+
+    // A failing callback aborts the evaluation
+    let result = encrypted.try_evaluate_with_checkpoints(&fixture.server_key, 3, |_| {
+        anyhow::bail!("disk full")
+    });
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_checkpoint_serialization_round_trip() {
+    let fixture = TestFixture::fast_demo();
+    let encrypted = mixed_circuit()
+        .encrypt_inputs(&[false, true, false], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    let mut saved = None;
+    encrypted
+        .try_evaluate_with_checkpoints(&fixture.server_key, 4, |checkpoint| {
+            saved = Some(checkpoint.to_bytes()?);
+            Ok(())
+        })
+        .expect("Evaluation with checkpoints failed");
+
+    let checkpoint = EvaluationCheckpoint::from_bytes(saved.expect("No checkpoint produced"))
+        .expect("Failed to deserialize checkpoint");
+    assert_eq!(checkpoint.next_gate(), 4);
+    assert_eq!(checkpoint.progress(), 0.5);
+
+    let result = EncryptedCircuit::resume_evaluation(checkpoint, &fixture.server_key)
+        .expect("Failed to resume evaluation");
+    // (0 & 1) ^ !0 | (0 & 0) = true
+    assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+
+    assert!(EvaluationCheckpoint::from_bytes(b"not a checkpoint".to_vec()).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_malformed_checkpoint_is_rejected() {
+    let fixture = TestFixture::fast_demo();
+    let encrypted = mixed_circuit()
+        .encrypt_inputs(&[false, true, false], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    let mut saved = None;
+    encrypted
+        .try_evaluate_with_checkpoints(&fixture.server_key, 4, |checkpoint| {
+            saved = Some(checkpoint.to_bytes()?);
+            Ok(())
+        })
+        .expect("Evaluation with checkpoints failed");
+
+    let checkpoint = EvaluationCheckpoint::from_bytes(saved.expect("No checkpoint produced"))
+        .expect("Failed to deserialize checkpoint");
+    for malformed in utils::malformed_circuits() {
+        let data = checkpoint.clone().with_circuit_unchecked(malformed).to_bytes().expect("Failed to serialize");
+        let error = EvaluationCheckpoint::from_bytes(data).unwrap_err().to_string();
+        assert!(error.contains("out of bounds") || error.contains("references future gate"), "{}", error);
+    }
+}
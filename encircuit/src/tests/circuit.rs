@@ -80,6 +80,31 @@ fn test_circuit_evaluation_with_constants() {
     assert!(decrypted); // Should be true due to OR with constant true
 }
 
+#[test]
+fn test_constant_only_circuit_evaluation() {
+    let fixture = TestFixture::fast_demo();
+
+    // Constants are materialized server-side as trivial ciphertexts
+    let mut builder = CircuitBuilder::default();
+    let const_true = builder.constant(true);
+    let output = builder.not(const_true);
+    let circuit = builder.finish(output);
+
+    let encrypted = circuit
+        .encrypt_inputs(&[], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+    assert_eq!(encrypted.input_count(), 0);
+
+    let result = encrypted
+        .try_evaluate(&fixture.server_key)
+        .expect("Failed to evaluate");
+    let decrypted = result[0]
+        .decrypt(&fixture.client_key)
+        .expect("Failed to decrypt result");
+
+    assert!(!decrypted);
+}
+
 #[test]
 fn test_circuit_validation() {
     // Test valid circuit using helper
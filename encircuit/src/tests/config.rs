@@ -0,0 +1,83 @@
+/*!
+Tests for parameter serialization, configuration loading and fingerprints.
+*/
+
+use crate::params::{scenario_to_tfhe_params, ParamsFingerprint};
+use crate::prelude::*;
+
+#[test]
+fn test_fingerprint_identifies_parameter_set() {
+    let fast = Params::for_scenario(Scenario::FastDemo).expect("Failed to create params");
+    let safe = Params::for_scenario(Scenario::SafeAndBalanced).expect("Failed to create params");
+    assert_ne!(fast.fingerprint(), safe.fingerprint());
+
+    // Only the TFHE parameters matter, not how they were selected
+    let custom = Params::custom(*scenario_to_tfhe_params(Scenario::FastDemo))
+        .expect("Failed to create custom params");
+    assert_eq!(custom.fingerprint(), fast.fingerprint());
+
+    let hex = fast.fingerprint().to_string();
+    assert_eq!(hex.len(), 64);
+    assert_eq!(hex.parse::<ParamsFingerprint>().expect("Failed to parse"), fast.fingerprint());
+    assert!("not a fingerprint".parse::<ParamsFingerprint>().is_err());
+    assert!("zz".repeat(32).parse::<ParamsFingerprint>().is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_params_serde_round_trip() {
+    let mut tuned = *scenario_to_tfhe_params(Scenario::FastDemo);
+    tuned.pbs_level = tfhe::boolean::parameters::DecompositionLevelCount(3);
+
+    let all = [
+        Params::for_scenario(Scenario::DeepCircuitUltraLowError).expect("Failed to create params"),
+        Params::custom(tuned).expect("Failed to create custom params"),
+    ];
+    for params in all {
+        let bytes = bincode::serialize(&params).expect("Failed to serialize params");
+        let restored: Params = bincode::deserialize(&bytes).expect("Failed to deserialize params");
+        assert_eq!(restored.scenario(), params.scenario());
+        assert_eq!(restored.is_custom(), params.is_custom());
+        assert_eq!(restored.fingerprint(), params.fingerprint());
+    }
+}
+
+#[cfg(feature = "config")]
+#[test]
+fn test_params_from_config_files() {
+    let params = Params::from_toml_str(r#"scenario = "SafeAndBalanced""#).expect("Failed to load TOML");
+    assert_eq!(params.scenario(), Scenario::SafeAndBalanced);
+
+    let params = Params::from_json_str(r#"{"scenario": "FastDemo"}"#).expect("Failed to load JSON");
+    assert_eq!(params.scenario(), Scenario::FastDemo);
+
+    let custom = r#"
+        [custom]
+        lwe_dimension = 739
+        glwe_dimension = 3
+        polynomial_size = 512
+        pbs_base_log = 10
+        pbs_level = 3
+        ks_base_log = 3
+        ks_level = 4
+        encryption_key_choice = "Big"
+
+        [custom.lwe_noise_distribution.Gaussian]
+        std = 1.8304520733507305e-05
+        mean = 0.0
+
+        [custom.glwe_noise_distribution.Gaussian]
+        std = 9.315272083503367e-10
+        mean = 0.0
+    "#;
+    let params = Params::from_toml_str(custom).expect("Failed to load custom TOML");
+    assert!(params.is_custom());
+    assert_eq!(params.tfhe_boolean_params().pbs_level.0, 3);
+
+    // Loaded custom parameters are validated
+    let invalid = custom.replace("polynomial_size = 512", "polynomial_size = 500");
+    let err = Params::from_toml_str(&invalid).expect_err("Invalid parameters were accepted");
+    assert!(err.to_string().contains("Polynomial size"), "{}", err);
+
+    assert!(Params::from_json_str(r#"{"scenario": "Unknown"}"#).is_err());
+}
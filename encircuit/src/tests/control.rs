@@ -0,0 +1,58 @@
+/*!
+Tests for evaluation cancellation and progress reporting.
+*/
+
+use crate::prelude::*;
+use crate::{CancellationToken, EvaluationProgress};
+use super::fixtures::{TestFixture, utils};
+
+#[test]
+fn test_cancellation_token_and_progress() {
+    let token = CancellationToken::new();
+    let clone = token.clone();
+    assert!(!token.is_cancelled());
+    clone.cancel();
+    assert!(token.is_cancelled());
+
+    let progress = EvaluationProgress { completed_gates: 1, total_gates: 4 };
+    assert!((progress.fraction() - 0.25).abs() < f64::EPSILON);
+    assert!(!progress.is_complete());
+    let empty = EvaluationProgress { completed_gates: 0, total_gates: 0 };
+    assert!((empty.fraction() - 1.0).abs() < f64::EPSILON);
+    assert!(empty.is_complete());
+}
+
+#[test]
+fn test_cancellable_evaluation() {
+    let fixture = TestFixture::fast_demo();
+    let encrypted = utils::xor_circuit()
+        .encrypt_inputs(&[true, false], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    // Progress is reported after every gate
+    let token = CancellationToken::new();
+    let mut reports = Vec::new();
+    let result = encrypted
+        .try_evaluate_cancellable(&fixture.server_key, &token, |progress| reports.push(progress))
+        .expect("Evaluation failed");
+    assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+    let completed: Vec<usize> = reports.iter().map(|progress| progress.completed_gates).collect();
+    assert_eq!(completed, vec![1, 2, 3]);
+    assert!(reports.last().is_some_and(EvaluationProgress::is_complete));
+
+    // Cancelling after the first input stops before the XOR is bootstrapped
+    let mut calls = 0;
+    let error = encrypted
+        .try_evaluate_cancellable(&fixture.server_key, &token, |_| {
+            calls += 1;
+            token.cancel();
+        })
+        .unwrap_err();
+    assert_eq!(calls, 1);
+    assert!(error.to_string().contains("cancelled"));
+
+    // An already cancelled token prevents the evaluation from starting
+    assert!(encrypted
+        .try_evaluate_cancellable(&fixture.server_key, &token, |_| panic!("No gate should run"))
+        .is_err());
+}
@@ -0,0 +1,112 @@
+/*!
+Tests for the debug evaluator with a client-key oracle.
+*/
+
+use crate::prelude::*;
+use crate::{MockBackend, MockCiphertext};
+use super::fixtures::{TestFixture, utils};
+
+/// A mock backend whose AND gates return the wrong value.
+#[derive(Default)]
+struct FaultyAndBackend(MockBackend);
+
+impl Backend for FaultyAndBackend {
+    type Ciphertext = MockCiphertext;
+    type Key = ();
+
+    fn constant(&self, key: &(), value: bool) -> MockCiphertext {
+        self.0.constant(key, value)
+    }
+
+    fn and(&self, key: &(), left: &MockCiphertext, right: &MockCiphertext) -> MockCiphertext {
+        MockCiphertext::encrypt(!self.0.and(key, left, right).decrypt())
+    }
+
+    fn or(&self, key: &(), left: &MockCiphertext, right: &MockCiphertext) -> MockCiphertext {
+        self.0.or(key, left, right)
+    }
+
+    fn xor(&self, key: &(), left: &MockCiphertext, right: &MockCiphertext) -> MockCiphertext {
+        self.0.xor(key, left, right)
+    }
+
+    fn not(&self, key: &(), input: &MockCiphertext) -> MockCiphertext {
+        self.0.not(key, input)
+    }
+}
+
+/// Build `(x XOR y) OR (x AND y)` with a labelled AND gate.
+fn labelled_circuit() -> Circuit {
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let y = builder.input();
+    let sum = builder.xor(x, y);
+    let carry = builder.and(x, y);
+    builder.label(carry, "carry");
+    let output = builder.or(sum, carry);
+    builder.finish(output)
+}
+
+#[test]
+fn test_debug_evaluation_consistent() {
+    let circuit = labelled_circuit();
+    let inputs = [true, true].map(MockCiphertext::encrypt);
+
+    let report = circuit
+        .evaluate_debug_with(&MockBackend::new(), &(), &inputs, &[], |ct| Ok(ct.decrypt()))
+        .expect("Debug evaluation failed");
+    assert!(report.is_consistent());
+    assert_eq!(report.checked_gates(), circuit.gate_count());
+    assert!(report.outputs()[0].decrypt());
+}
+
+#[test]
+fn test_debug_evaluation_reports_first_divergence() {
+    let circuit = labelled_circuit();
+    let inputs = [true, true].map(MockCiphertext::encrypt);
+
+    let report = circuit
+        .evaluate_debug_with(&FaultyAndBackend::default(), &(), &inputs, &[], |ct| Ok(ct.decrypt()))
+        .expect("Debug evaluation failed");
+
+    // The faulty AND diverges first; the OR downstream of it is not reported
+    let divergence = report.divergence().expect("Divergence not detected");
+    assert_eq!(divergence.node.id(), 3);
+    assert_eq!(divergence.gate_type, "AND");
+    assert_eq!(divergence.level, 1);
+    assert_eq!(divergence.label.as_deref(), Some("carry"));
+    assert!(divergence.expected);
+    assert!(!divergence.actual);
+    assert_eq!(
+        divergence.to_string(),
+        "AND gate 3 at level 1 (carry) decrypted to false, expected true"
+    );
+
+    // Evaluation still runs to completion
+    assert_eq!(report.checked_gates(), circuit.gate_count());
+    assert!(!report.into_outputs()[0].decrypt());
+}
+
+#[test]
+fn test_debug_evaluation_input_count_mismatch() {
+    let circuit = labelled_circuit();
+    let inputs = [MockCiphertext::encrypt(true)];
+
+    let result = circuit.evaluate_debug_with(&MockBackend::new(), &(), &inputs, &[], |ct| Ok(ct.decrypt()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_debug_evaluation_with_client_key() {
+    let fixture = TestFixture::fast_demo();
+    let encrypted = utils::xor_circuit()
+        .encrypt_inputs(&[true, false], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+
+    let report = encrypted
+        .try_evaluate_debug(&fixture.server_key, &fixture.client_key)
+        .expect("Debug evaluation failed");
+    assert!(report.is_consistent());
+    assert_eq!(report.checked_gates(), 3);
+    assert!(report.outputs()[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
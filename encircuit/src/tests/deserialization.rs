@@ -0,0 +1,78 @@
+/*!
+Tests for bounded deserialization and parameter conformance checks.
+*/
+
+use crate::prelude::*;
+use crate::DeserializationConfig;
+use super::fixtures::TestFixture;
+
+#[test]
+fn test_safe_from_bytes_round_trip() {
+    let fast = TestFixture::fast_demo();
+    let config = DeserializationConfig::new(1 << 30).with_expected_params(&fast.params);
+
+    let ciphertext = true.encrypt(&fast.client_key).expect("Failed to encrypt");
+    let bytes = ciphertext.to_bytes().expect("Failed to serialize ciphertext");
+    let restored = BoolCt::safe_from_bytes(&bytes, &config).expect("Conforming ciphertext rejected");
+    assert!(restored.decrypt(&fast.client_key).expect("Failed to decrypt"));
+
+    let client_bytes = fast.client_key.as_bytes().expect("Failed to serialize client key");
+    let client_key = ClientKeyBytes::safe_from_bytes(&client_bytes, &config).expect("Conforming client key rejected");
+    assert_eq!(client_key.id(), fast.client_key.id());
+
+    let server_bytes = fast.server_key.as_bytes().expect("Failed to serialize server key");
+    let server_key = ServerKeyBytes::safe_from_bytes(&server_bytes, &config).expect("Conforming server key rejected");
+    assert_eq!(server_key.id(), fast.server_key.id());
+}
+
+#[test]
+fn test_size_limit_rejects_oversized_and_forged_data() {
+    let fast = TestFixture::fast_demo();
+    let ciphertext = true.encrypt(&fast.client_key).expect("Failed to encrypt");
+    let bytes = ciphertext.to_bytes().expect("Failed to serialize ciphertext");
+
+    let tight = DeserializationConfig::new(bytes.len() as u64 - 1);
+    let error = BoolCt::safe_from_bytes(&bytes, &tight).unwrap_err().to_string();
+    assert!(error.contains("exceeding the limit"), "{}", error);
+
+    // A forged length prefix must fail without allocating what it claims
+    let mut forged = bytes.clone();
+    let prefix = (0..bytes.len() - 8)
+        .find(|&i| {
+            // The length of the ciphertext coefficient vector, followed by the coefficients
+            let len = u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap()) as usize;
+            len > 1 && len * 4 <= bytes.len() - i - 8 && len * 4 + 64 >= bytes.len() - i - 8
+        })
+        .expect("Coefficient length prefix not found");
+    forged[prefix..prefix + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    let config = DeserializationConfig::new(1 << 20);
+    assert!(BoolCt::safe_from_bytes(&forged, &config).is_err());
+
+    // Trailing garbage is rejected too
+    let mut padded = bytes;
+    padded.push(0);
+    assert!(BoolCt::safe_from_bytes(&padded, &config).is_err());
+}
+
+#[test]
+fn test_conformance_rejects_other_parameters() {
+    let fast = TestFixture::fast_demo();
+    let safe = TestFixture::safe_balanced();
+
+    let ciphertext = true.encrypt(&fast.client_key).expect("Failed to encrypt");
+    ciphertext.check_conformance(&fast.params).expect("Ciphertext should conform");
+    let error = ciphertext.check_conformance(&safe.params).unwrap_err().to_string();
+    assert!(error.contains("LWE size"), "{}", error);
+
+    let config = DeserializationConfig::new(1 << 30).with_expected_params(&safe.params);
+    let bytes = ciphertext.to_bytes().expect("Failed to serialize ciphertext");
+    assert!(BoolCt::safe_from_bytes(&bytes, &config).is_err());
+
+    fast.client_key.check_conformance(&fast.params).expect("Client key should conform");
+    let error = fast.client_key.check_conformance(&safe.params).unwrap_err().to_string();
+    assert!(error.contains("Client key doesn't conform"), "{}", error);
+
+    fast.server_key.check_conformance(&fast.params).expect("Server key should conform");
+    let error = fast.server_key.check_conformance(&safe.params).unwrap_err().to_string();
+    assert!(error.contains("Server key doesn't conform"), "{}", error);
+}
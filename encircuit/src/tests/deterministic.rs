@@ -0,0 +1,55 @@
+/*!
+Tests for seeded key generation and encryption.
+*/
+
+use crate::prelude::*;
+use super::fixtures::{utils, TestFixture};
+
+#[test]
+fn test_seeded_encryption_is_reproducible() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = utils::and_circuit();
+    let bytes = |encrypted: &EncryptedCircuit| -> Vec<Vec<u8>> {
+        encrypted
+            .encrypted_inputs()
+            .iter()
+            .map(|ct| ct.to_bytes().expect("Failed to serialize ciphertext"))
+            .collect()
+    };
+
+    let first = circuit
+        .encrypt_inputs_from_seed(&[true, false], &fixture.client_key, [1; 32])
+        .expect("Failed to encrypt");
+    let again = circuit
+        .encrypt_inputs_from_seed(&[true, false], &fixture.client_key, [1; 32])
+        .expect("Failed to encrypt");
+    let other = circuit
+        .encrypt_inputs_from_seed(&[true, false], &fixture.client_key, [2; 32])
+        .expect("Failed to encrypt");
+    assert_eq!(bytes(&first), bytes(&again));
+    assert_ne!(bytes(&first), bytes(&other));
+
+    // Regular encryption is randomized again afterwards
+    let random = circuit.encrypt_inputs(&[true, false], &fixture.client_key).expect("Failed to encrypt");
+    let random_again = circuit.encrypt_inputs(&[true, false], &fixture.client_key).expect("Failed to encrypt");
+    assert_ne!(bytes(&random), bytes(&random_again));
+
+    let result = first.evaluate(&fixture.server_key);
+    assert!(!result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
+
+#[test]
+fn test_seeded_key_generation_is_reproducible() {
+    let params = Params::for_scenario(Scenario::FastDemo).expect("Failed to create params");
+    let first = Keyset::generate_from_seed(&params, [3; 32]).expect("Failed to generate keys");
+    let again = Keyset::generate_from_seed(&params, [3; 32]).expect("Failed to generate keys");
+
+    assert_eq!(
+        first.client_key().as_bytes().expect("Failed to serialize client key"),
+        again.client_key().as_bytes().expect("Failed to serialize client key")
+    );
+    assert_eq!(
+        first.server_key().as_bytes().expect("Failed to serialize server key"),
+        again.server_key().as_bytes().expect("Failed to serialize server key")
+    );
+}
@@ -0,0 +1,128 @@
+/*!
+Tests for execution configurations and parallel evaluation.
+*/
+
+use crate::prelude::*;
+use crate::{ExecutionConfig, MockBackend, MockCiphertext, PlaintextBackend};
+use super::fixtures::{TestFixture, utils};
+use std::sync::Arc;
+
+/// Configurations covering every execution mode.
+fn all_configs() -> Vec<ExecutionConfig> {
+    vec![
+        ExecutionConfig::sequential(),
+        ExecutionConfig::global(),
+        ExecutionConfig::threads(2).expect("Failed to create thread pool"),
+    ]
+}
+
+/// Build a wide circuit with a public input: `(a AND b) XOR (c OR p) XOR NOT d`.
+fn wide_circuit() -> Circuit {
+    let mut builder = CircuitBuilder::default();
+    let a = builder.input();
+    let b = builder.input();
+    let c = builder.input();
+    let d = builder.input();
+    let p = builder.public_input();
+    let ab = builder.and(a, b);
+    let cp = builder.or(c, p);
+    let not_d = builder.not(d);
+    let left = builder.xor(ab, cp);
+    let output = builder.xor(left, not_d);
+    builder.finish(output)
+}
+
+#[test]
+fn test_execution_config_construction() {
+    assert!(ExecutionConfig::sequential().is_sequential());
+    assert_eq!(ExecutionConfig::sequential().thread_count(), 1);
+
+    let config = ExecutionConfig::threads(3).expect("Failed to create thread pool");
+    assert!(!config.is_sequential());
+    assert_eq!(config.thread_count(), 3);
+    assert!(ExecutionConfig::threads(0).is_err());
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .expect("Failed to create thread pool");
+    assert_eq!(ExecutionConfig::with_pool(Arc::new(pool)).thread_count(), 2);
+
+    assert_eq!(ExecutionConfig::default().is_sequential(), !cfg!(feature = "parallel"));
+}
+
+#[test]
+fn test_parallel_evaluation_matches_sequential() {
+    let circuit = wide_circuit();
+
+    for config in all_configs() {
+        for bits in 0..32u8 {
+            let inputs = [bits & 1 != 0, bits & 2 != 0, bits & 4 != 0, bits & 8 != 0];
+            let public = [bits & 16 != 0];
+            let expected = circuit
+                .evaluate_with_public_inputs(&PlaintextBackend, &(), &inputs, &public)
+                .expect("Sequential evaluation failed");
+            let result = circuit
+                .evaluate_with_config(&PlaintextBackend, &(), &inputs, &public, &config)
+                .expect("Parallel evaluation failed");
+            assert_eq!(result, expected, "config {:?}, inputs {:?}", config, inputs);
+        }
+    }
+}
+
+#[test]
+fn test_parallel_evaluation_folds_constants() {
+    let circuit = wide_circuit();
+    let inputs = [true, true, false, true].map(MockCiphertext::encrypt);
+    let config = ExecutionConfig::threads(2).expect("Failed to create thread pool");
+
+    // `c OR true` folds to true, so the second XOR becomes a NOT
+    let backend = MockBackend::new();
+    let result = circuit
+        .evaluate_with_config(&backend, &(), &inputs, &[true], &config)
+        .expect("Parallel evaluation failed");
+    assert!(!result[0].decrypt());
+    assert_eq!(backend.bootstrap_count(), 2);
+    assert_eq!(backend.not_count(), 2);
+
+    // Constant outputs are materialized once
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let zero = builder.constant(false);
+    let output = builder.and(x, zero);
+    let circuit = builder.finish(output);
+    let backend = MockBackend::new();
+    let result = circuit
+        .evaluate_with_config(&backend, &(), &[MockCiphertext::encrypt(true)], &[], &config)
+        .expect("Parallel evaluation failed");
+    assert!(!result[0].decrypt());
+    assert_eq!(backend.constant_count(), 1);
+
+    assert!(circuit
+        .evaluate_with_config(&backend, &(), &[], &[], &config)
+        .is_err());
+}
+
+#[test]
+fn test_encrypt_and_evaluate_with_config() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = utils::xor_circuit();
+    let config = ExecutionConfig::threads(2).expect("Failed to create thread pool");
+
+    let first = circuit
+        .encrypt_inputs_with_config(&[true, false], &fixture.client_key, &config)
+        .expect("Failed to encrypt inputs");
+    let result = first
+        .try_evaluate_with_config(&fixture.server_key, &config)
+        .expect("Evaluation failed");
+    assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+
+    let second = circuit
+        .encrypt_inputs_with_config(&[true, true], &fixture.client_key, &ExecutionConfig::sequential())
+        .expect("Failed to encrypt inputs");
+    let results = EncryptedCircuit::try_evaluate_batch(&[first, second], &fixture.server_key, &config)
+        .expect("Batch evaluation failed");
+    assert_eq!(results.len(), 2);
+    assert!(results[0][0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+    assert!(!results[1][0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
@@ -133,4 +133,19 @@ pub mod utils {
         
         Ok(())
     }
+
+    /// Build malformed circuits that deserialization must reject.
+    ///
+    /// They have an out-of-range gate operand, an out-of-range output and an
+    /// empty gate list, respectively.
+    #[cfg(feature = "serde")]
+    pub fn malformed_circuits() -> Vec<crate::circuit::Circuit> {
+        use crate::circuit::{Circuit, Gate, NodeId};
+
+        vec![
+            Circuit::new_unchecked(vec![Gate::Input, Gate::Input, Gate::And(NodeId(0), NodeId(99))], NodeId(2)),
+            Circuit::new_unchecked(vec![Gate::Input, Gate::Input, Gate::And(NodeId(0), NodeId(1))], NodeId(99)),
+            Circuit::new_unchecked(Vec::new(), NodeId(0)),
+        ]
+    }
 }
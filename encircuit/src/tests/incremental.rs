@@ -0,0 +1,121 @@
+/*!
+Tests for incremental re-evaluation.
+*/
+
+use crate::prelude::*;
+use crate::{IncrementalEvaluator, MockBackend, MockCiphertext, PlaintextBackend, TfheBackend};
+use super::fixtures::TestFixture;
+
+/// Build `((a AND b) XOR c) OR (NOT d)` with four inputs.
+fn four_input_circuit() -> Circuit {
+    let mut builder = CircuitBuilder::default();
+    let a = builder.input();
+    let b = builder.input();
+    let c = builder.input();
+    let d = builder.input();
+    let ab = builder.and(a, b);
+    let abc = builder.xor(ab, c);
+    let not_d = builder.not(d);
+    let output = builder.or(abc, not_d);
+    builder.finish(output)
+}
+
+#[test]
+fn test_update_recomputes_only_fan_out() {
+    let mut evaluator = IncrementalEvaluator::new(MockBackend::new(), four_input_circuit());
+    let inputs = [true, true, true, true].map(MockCiphertext::encrypt).to_vec();
+
+    let result = evaluator.evaluate(&(), inputs, &[]).expect("Evaluation failed");
+    assert!(!result[0].decrypt());
+    assert_eq!(evaluator.last_recomputed_gates(), 8);
+    assert_eq!(evaluator.backend().bootstrap_count(), 3);
+
+    // `d` only feeds the NOT and the final OR
+    evaluator.backend().reset_counts();
+    let result = evaluator
+        .update(&(), vec![(3, MockCiphertext::encrypt(false))])
+        .expect("Update failed");
+    assert!(result[0].decrypt());
+    assert_eq!(evaluator.last_recomputed_gates(), 2);
+    assert_eq!(evaluator.backend().bootstrap_count(), 1);
+    assert_eq!(evaluator.backend().not_count(), 1);
+
+    // `a` feeds the AND, the XOR and the OR
+    evaluator.backend().reset_counts();
+    evaluator
+        .update(&(), vec![(0, MockCiphertext::encrypt(false))])
+        .expect("Update failed");
+    assert_eq!(evaluator.last_recomputed_gates(), 3);
+    assert_eq!(evaluator.backend().bootstrap_count(), 3);
+    assert_eq!(evaluator.backend().not_count(), 0);
+}
+
+#[test]
+fn test_updates_match_full_evaluation() {
+    let circuit = four_input_circuit();
+    let mut evaluator = IncrementalEvaluator::new(PlaintextBackend, circuit.clone());
+    let mut inputs = vec![false; 4];
+    evaluator.evaluate(&(), inputs.clone(), &[]).expect("Evaluation failed");
+
+    // Walk through every input combination by flipping one or two bits at a time
+    for step in 1..32usize {
+        let mut replaced = vec![(step % 4, !inputs[step % 4])];
+        if step % 3 == 0 {
+            let other = (step + 1) % 4;
+            replaced.push((other, !inputs[other]));
+        }
+        for (input, value) in &replaced {
+            inputs[*input] = *value;
+        }
+
+        let result = evaluator.update(&(), replaced).expect("Update failed");
+        let expected = circuit
+            .evaluate_with(&PlaintextBackend, &(), &inputs)
+            .expect("Evaluation failed");
+        assert_eq!(result, expected, "inputs {:?}", inputs);
+    }
+}
+
+#[test]
+fn test_update_errors() {
+    let mut evaluator = IncrementalEvaluator::new(PlaintextBackend, four_input_circuit());
+
+    // Updating requires a previous full evaluation
+    assert!(!evaluator.is_evaluated());
+    assert!(evaluator.update(&(), vec![(0, true)]).is_err());
+
+    assert!(evaluator.evaluate(&(), vec![true; 3], &[]).is_err());
+    evaluator.evaluate(&(), vec![true; 4], &[]).expect("Evaluation failed");
+    assert!(evaluator.is_evaluated());
+
+    let error = evaluator.update(&(), vec![(4, true)]).unwrap_err();
+    assert!(error.to_string().contains("out of range"));
+}
+
+#[test]
+fn test_incremental_tfhe_evaluation() {
+    let fixture = TestFixture::fast_demo();
+    let server_key = fixture.server_key.tfhe_key().expect("Invalid server key");
+
+    // A single XOR keeps the number of bootstraps low
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let y = builder.input();
+    let output = builder.xor(x, y);
+    let circuit = builder.finish(output);
+
+    let encrypted = circuit
+        .encrypt_inputs(&[true, false], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+    let mut evaluator = IncrementalEvaluator::new(TfheBackend, circuit);
+    let result = evaluator
+        .evaluate(server_key, encrypted.encrypted_inputs().to_vec(), &[])
+        .expect("Evaluation failed");
+    assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+
+    let replacement = true.encrypt(&fixture.client_key).expect("Failed to encrypt");
+    let result = evaluator
+        .update(server_key, vec![(1, replacement)])
+        .expect("Update failed");
+    assert!(!result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
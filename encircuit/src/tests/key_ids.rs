@@ -0,0 +1,84 @@
+/*!
+Tests for key ids and key mismatch detection.
+*/
+
+use crate::prelude::*;
+use crate::ExecutionConfig;
+use super::fixtures::{TestFixture, utils};
+
+#[test]
+fn test_key_ids_identify_keysets() {
+    let fast = TestFixture::fast_demo();
+    let safe = TestFixture::safe_balanced();
+
+    assert_eq!(fast.client_key.id(), fast.server_key.id());
+    assert_ne!(fast.client_key.id(), safe.client_key.id());
+    assert_eq!(fast.client_key.id().to_string().len(), 32);
+
+    let ciphertext = true.encrypt(&fast.client_key).expect("Failed to encrypt");
+    assert_eq!(ciphertext.key_id(), Some(fast.client_key.id()));
+}
+
+#[test]
+fn test_evaluation_with_other_server_key_fails() {
+    let fast = TestFixture::fast_demo();
+    let safe = TestFixture::safe_balanced();
+
+    let encrypted = utils::and_circuit()
+        .encrypt_inputs(&[true, true], &fast.client_key)
+        .expect("Failed to encrypt inputs");
+    assert_eq!(encrypted.key_id(), Some(fast.client_key.id()));
+
+    let error = encrypted.try_evaluate(&safe.server_key).unwrap_err().to_string();
+    assert!(error.contains("Ciphertext for input 0 was encrypted under key"), "{}", error);
+    assert!(error.contains(&safe.server_key.id().to_string()), "{}", error);
+
+    let batch = [encrypted];
+    let error = EncryptedCircuit::try_evaluate_batch(&batch, &safe.server_key, &ExecutionConfig::sequential())
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("Batch entry 0"), "{}", error);
+}
+
+#[test]
+fn test_decryption_with_other_client_key_fails() {
+    let fast = TestFixture::fast_demo();
+    let safe = TestFixture::safe_balanced();
+
+    let ciphertext = false.encrypt(&fast.client_key).expect("Failed to encrypt");
+    let error = ciphertext.decrypt(&safe.client_key).unwrap_err().to_string();
+    assert!(error.contains("but the client key is"), "{}", error);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_key_ids_survive_serialization() {
+    let fast = TestFixture::fast_demo();
+    let safe = TestFixture::safe_balanced();
+
+    let server_key = ServerKeyBytes::from_bytes(fast.server_key.as_bytes().unwrap()).unwrap();
+    assert_eq!(server_key.id(), fast.server_key.id());
+
+    let ciphertext = BoolCt::from_bytes(true.encrypt(&fast.client_key).unwrap().to_bytes().unwrap()).unwrap();
+    assert_eq!(ciphertext.key_id(), Some(fast.client_key.id()));
+
+    let encrypted = utils::and_circuit()
+        .encrypt_inputs(&[true, false], &fast.client_key)
+        .unwrap();
+    let restored = EncryptedCircuit::from_bytes(encrypted.to_bytes().unwrap()).unwrap();
+    assert_eq!(restored.key_id(), Some(fast.client_key.id()));
+    assert!(restored.try_evaluate(&safe.server_key).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_malformed_encrypted_circuit_is_rejected() {
+    let fast = TestFixture::fast_demo();
+    let encrypted = utils::and_circuit().encrypt_inputs(&[true, false], &fast.client_key).unwrap();
+
+    for malformed in utils::malformed_circuits() {
+        let data = encrypted.clone().with_circuit_unchecked(malformed).to_bytes().unwrap();
+        let error = EncryptedCircuit::from_bytes(data).unwrap_err().to_string();
+        assert!(error.contains("Invalid encrypted circuit"), "{}", error);
+    }
+}
@@ -89,6 +89,45 @@ mod serde_tests {
         let _tfhe_key = deserialized.tfhe_key().expect("Failed to get TFHE key from deserialized server key");
     }
 
+    #[test]
+    fn test_server_key_streaming_round_trip() {
+        let fixture = TestFixture::fast_demo();
+
+        // Streaming uses the same format as `as_bytes`
+        let mut streamed = Vec::new();
+        fixture.server_key.write_to(&mut streamed).expect("Failed to write server key");
+        assert_eq!(streamed, fixture.server_key.as_bytes().expect("Failed to serialize server key"));
+
+        let restored = ServerKeyBytes::read_from(std::io::Cursor::new(&streamed))
+            .expect("Failed to read server key");
+        assert_eq!(restored.id(), fixture.server_key.id());
+
+        let circuit = crate::tests::fixtures::utils::and_circuit();
+        let encrypted = circuit.encrypt_inputs(&[true, true], &fixture.client_key).expect("Failed to encrypt");
+        let result = encrypted.evaluate(&restored);
+        assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+
+        // A truncated stream fails instead of yielding a partial key
+        let truncated = &streamed[..streamed.len() / 2];
+        assert!(ServerKeyBytes::read_from(truncated).is_err());
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_server_key_from_mapped_file() {
+        let fixture = TestFixture::fast_demo();
+        let path = std::env::temp_dir().join(format!("encircuit-mmap-test-{}.key", std::process::id()));
+
+        let file = std::fs::File::create(&path).expect("Failed to create key file");
+        fixture.server_key.write_to(std::io::BufWriter::new(file)).expect("Failed to write server key");
+        let restored = ServerKeyBytes::from_mapped_file(&path);
+        std::fs::remove_file(&path).expect("Failed to remove key file");
+        assert_eq!(restored.expect("Failed to load mapped server key").id(), fixture.server_key.id());
+
+        let error = ServerKeyBytes::from_mapped_file(&path).unwrap_err().to_string();
+        assert!(error.contains("Failed to open server key"), "{}", error);
+    }
+
     #[test]
     fn test_keyset_serialization_round_trip() {
         let fixture = TestFixture::fast_demo();
@@ -149,6 +188,102 @@ mod serde_tests {
         println!("Client key serialized size: {} bytes", client_bytes.len());
         println!("Server key serialized size: {} bytes", server_bytes.len());
     }
+
+    #[test]
+    fn test_compressed_server_key_transport() {
+        use crate::keys::CompressedServerKeyBytes;
+
+        let params = Params::for_scenario(Scenario::FastDemo).expect("Failed to create params");
+        let (client_key, compressed) = Keyset::generate_compressed(&params)
+            .expect("Failed to generate compressed keys");
+
+        // Ship the compressed key and expand it on the "server"
+        let compressed_bytes = compressed.as_bytes().expect("Failed to serialize compressed key");
+        let received = CompressedServerKeyBytes::from_bytes(compressed_bytes.clone())
+            .expect("Failed to deserialize compressed key");
+        let server_key = received.decompress();
+
+        let server_bytes = server_key.as_bytes().expect("Failed to serialize server key");
+        assert!(
+            compressed_bytes.len() < server_bytes.len(),
+            "Compressed key ({} bytes) should be smaller than the server key ({} bytes)",
+            compressed_bytes.len(),
+            server_bytes.len()
+        );
+
+        // The decompressed key evaluates circuits encrypted with the client key
+        let circuit = crate::tests::fixtures::utils::and_circuit();
+        let encrypted = circuit.encrypt_inputs(&[true, true], &client_key).expect("Failed to encrypt");
+        let result = encrypted.evaluate(&server_key);
+        assert!(result[0].decrypt(&client_key).expect("Failed to decrypt"));
+
+        assert!(CompressedServerKeyBytes::from_bytes(b"not a key".to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_passphrase_encrypted_client_key() {
+        let fixture = TestFixture::fast_demo();
+
+        let sealed = fixture.client_key.to_encrypted_bytes("correct horse").expect("Failed to encrypt key");
+        let restored = ClientKeyBytes::from_encrypted_bytes(&sealed, "correct horse").expect("Failed to decrypt key");
+        assert_eq!(restored.id(), fixture.client_key.id());
+
+        let error = ClientKeyBytes::from_encrypted_bytes(&sealed, "wrong horse").unwrap_err().to_string();
+        assert!(error.contains("wrong passphrase or corrupted data"), "{}", error);
+
+        // Tampering with the salt is detected as well as with the ciphertext
+        for index in [20, sealed.len() - 1] {
+            let mut tampered = sealed.clone();
+            tampered[index] ^= 1;
+            assert!(ClientKeyBytes::from_encrypted_bytes(&tampered, "correct horse").is_err());
+        }
+
+        let mut future = sealed.clone();
+        future[4] = 2;
+        let error = ClientKeyBytes::from_encrypted_bytes(&future, "correct horse").unwrap_err().to_string();
+        assert!(error.contains("Unsupported container version 2"), "{}", error);
+
+        let raw = fixture.client_key.as_bytes().expect("Failed to serialize client key");
+        let error = ClientKeyBytes::from_encrypted_bytes(&raw, "correct horse").unwrap_err().to_string();
+        assert!(error.contains("not a passphrase-encrypted container"), "{}", error);
+
+        assert!(fixture.client_key.to_encrypted_bytes("").is_err());
+    }
+
+    #[test]
+    fn test_serialized_client_key_is_secret() {
+        let fixture = TestFixture::fast_demo();
+
+        let bytes: crate::SecretBytes = fixture.client_key.as_bytes().expect("Failed to serialize client key");
+        let debug = format!("{:?}", bytes);
+        assert!(debug.contains("<redacted>"), "{}", debug);
+        assert!(debug.contains(&format!("len: {}", bytes.len())), "{}", debug);
+
+        // Round-trips through both the secret buffer and a plain copy
+        let restored = ClientKeyBytes::from_bytes(bytes.clone()).expect("Failed to deserialize client key");
+        assert_eq!(restored.id(), fixture.client_key.id());
+        let restored = ClientKeyBytes::from_bytes(bytes.to_vec()).expect("Failed to deserialize client key");
+        assert_eq!(restored.as_bytes().expect("Failed to serialize client key"), bytes);
+    }
+}
+
+#[test]
+fn test_client_key_copies_share_secret() {
+    let fixture = TestFixture::fast_demo();
+
+    // Cloning the keyset and splitting it doesn't copy the secret key
+    let (client_key, _) = fixture.keyset.clone().split();
+    let shared = client_key.clone();
+    assert!(std::ptr::eq(
+        client_key.tfhe_key().unwrap(),
+        fixture.keyset.client_key().tfhe_key().unwrap()
+    ));
+    assert!(std::ptr::eq(shared.tfhe_key().unwrap(), client_key.tfhe_key().unwrap()));
+
+    // Dropping a copy leaves the others usable
+    drop(client_key);
+    let encrypted = true.encrypt(&shared).expect("Failed to encrypt");
+    assert!(encrypted.decrypt(&fixture.client_key).expect("Failed to decrypt"));
 }
 
 #[cfg(not(feature = "serde"))]
@@ -168,3 +303,30 @@ mod no_serde_tests {
         assert!(server_result.is_err());
     }
 }
+
+#[test]
+fn test_public_key_encryption() {
+    let fixture = TestFixture::fast_demo();
+    let public_key = fixture.client_key.public_key();
+
+    // Data producers only hold the (serialized) public key
+    #[cfg(feature = "serde")]
+    let public_key = {
+        let bytes = public_key.as_bytes().expect("Failed to serialize public key");
+        PublicKeyBytes::from_bytes(bytes).expect("Failed to deserialize public key")
+    };
+
+    // NOT is free to evaluate, so the test only pays for one public-key encryption
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let out = builder.not(x);
+    let circuit = builder.finish(out);
+
+    assert!(circuit.encrypt_inputs_with_public_key(&[], &public_key).is_err());
+
+    let encrypted = circuit
+        .encrypt_inputs_with_public_key(&[true], &public_key)
+        .expect("Failed to encrypt with public key");
+    let result = encrypted.evaluate(&fixture.server_key);
+    assert!(!result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
@@ -10,7 +10,30 @@ mod fixtures;
 
 // Test modules organized by functionality
 mod params;       // Parameter configuration and scenario tests
+mod config;       // Parameter serialization, configuration files and fingerprints
 mod keys;         // Key generation and serialization tests  
 mod circuit;      // Circuit building, validation, and evaluation tests
+mod checkpoint;   // Evaluation checkpoint and resume tests
+mod profile;      // Labels, levels, and evaluation profiling tests
 mod ciphertext;   // Ciphertext encryption and decryption tests
+mod key_ids;      // Key id and key mismatch tests
+#[cfg(feature = "serde")]
+mod sharing;      // Threshold secret sharing of client keys
+#[cfg(feature = "serde")]
+mod deserialization; // Bounded deserialization and conformance checks
+mod backend;      // Pluggable evaluation backend tests
+mod public_inputs; // Public (cleartext) input tests
+mod assembly;     // Multi-contributor input assembly tests
+mod incremental;  // Incremental re-evaluation tests
+mod execution;    // Execution configuration and parallel evaluation tests
+mod redundancy;   // Redundant evaluation and majority voting tests
+mod control;      // Cancellation and progress reporting tests
+#[cfg(feature = "async")]
+mod asynchronous; // Async evaluation API tests
+#[cfg(feature = "debug-oracle")]
+mod debug;        // Client-key oracle debug evaluation tests
+#[cfg(feature = "insecure-deterministic-rng")]
+mod deterministic; // Seeded key generation and encryption tests
+#[cfg(feature = "key-cache")]
+mod cache;        // On-disk keyset cache tests
 mod integration;  // End-to-end integration tests
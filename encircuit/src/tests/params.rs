@@ -89,3 +89,140 @@ fn test_params_tfhe_boolean_params_with_scenario() {
     
     assert!(is_different, "Deep circuit parameters should differ from default");
 }
+
+#[test]
+fn test_custom_params() {
+    use tfhe::boolean::parameters::DecompositionLevelCount;
+
+    let mut tuned = *Params::for_scenario(Scenario::FastDemo)
+        .expect("Failed to create params")
+        .tfhe_boolean_params();
+    tuned.pbs_level = DecompositionLevelCount(tuned.pbs_level.0 + 1);
+
+    let params = Params::custom(tuned).expect("Failed to create custom params");
+    assert!(params.is_custom());
+    assert_eq!(params.custom_parameters(), Some(&tuned));
+    assert_eq!(params.scenario(), Scenario::SafeAndBalanced);
+    assert!(!Params::for_scenario(Scenario::FastDemo).unwrap().is_custom());
+    assert_eq!(*params.tfhe_boolean_params(), tuned);
+
+    // Every preset passes validation
+    for scenario in [
+        Scenario::SafeAndBalanced,
+        Scenario::DeepCircuitUltraLowError,
+        Scenario::FastDemo,
+        Scenario::MaxSecurityMinimalOps,
+    ] {
+        let preset = *crate::params::scenario_to_tfhe_params(scenario);
+        assert!(Params::custom(preset).is_ok(), "{:?} preset rejected", scenario);
+    }
+}
+
+#[test]
+fn test_custom_params_validation() {
+    use tfhe::boolean::parameters::*;
+
+    let base = *crate::params::scenario_to_tfhe_params(Scenario::FastDemo);
+    let invalid: Vec<(&str, BooleanParameters)> = vec![
+        ("LWE dimension", BooleanParameters { lwe_dimension: LweDimension(0), ..base }),
+        ("GLWE dimension", BooleanParameters { glwe_dimension: GlweDimension(0), ..base }),
+        ("Polynomial size", BooleanParameters { polynomial_size: PolynomialSize(1000), ..base }),
+        ("PBS decomposition", BooleanParameters { pbs_level: DecompositionLevelCount(0), ..base }),
+        (
+            "Key switching decomposition uses 40 bits",
+            BooleanParameters {
+                ks_base_log: DecompositionBaseLog(8),
+                ks_level: DecompositionLevelCount(5),
+                ..base
+            },
+        ),
+        (
+            "LWE noise standard deviation",
+            BooleanParameters {
+                lwe_noise_distribution: DynamicDistribution::new_gaussian_from_std_dev(StandardDev(2.0)),
+                ..base
+            },
+        ),
+        (
+            "GLWE noise standard deviation",
+            BooleanParameters {
+                glwe_noise_distribution: DynamicDistribution::new_gaussian_from_std_dev(StandardDev(f64::NAN)),
+                ..base
+            },
+        ),
+    ];
+
+    for (expected, params) in invalid {
+        let err = Params::custom(params).expect_err("Invalid parameters were accepted");
+        assert!(
+            err.to_string().contains(expected),
+            "Expected error mentioning {:?}, got: {}",
+            expected,
+            err
+        );
+    }
+}
+
+#[test]
+fn test_for_security_picks_least_costly_set() {
+    let report = Params::for_security(SecurityLevel::Bits128, ErrorProbability::Pow2Minus64)
+        .expect("Failed to select params");
+    assert_eq!(report.params().scenario(), Scenario::FastDemo);
+    assert_eq!(report.selected().scenario, Scenario::FastDemo);
+
+    // Candidates are ordered by cost and exactly one is selected
+    let costs: Vec<u32> = report.candidates().iter().map(|c| c.info.cost).collect();
+    assert!(costs.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert_eq!(report.candidates().iter().filter(|c| c.selected).count(), 1);
+
+    let report = Params::for_security(SecurityLevel::Bits128, ErrorProbability::Pow2Minus128)
+        .expect("Failed to select params");
+    assert_eq!(report.params().scenario(), Scenario::MaxSecurityMinimalOps);
+    let rejected = report.candidates().iter().filter(|c| !c.meets_error_probability).count();
+    assert_eq!(rejected, 2);
+
+    let explanation = report.to_string();
+    assert!(explanation.contains("Selected MaxSecurityMinimalOps"));
+    assert!(explanation.contains("rejected: error probability too high"));
+}
+
+#[test]
+fn test_for_security_rejects_unreachable_targets() {
+    let err = Params::for_security(SecurityLevel::Bits192, ErrorProbability::Pow2Minus40)
+        .expect_err("No built-in set provides 192-bit security");
+    assert!(err.to_string().contains("192-bit security"));
+}
+
+#[test]
+fn test_recommend_for_scales_with_circuit_size() {
+    // A chain of n AND gates performs n bootstraps
+    let chain = |n: usize| {
+        let mut builder = CircuitBuilder::default();
+        let mut acc = builder.input();
+        for _ in 0..n {
+            let x = builder.input();
+            acc = builder.and(acc, x);
+        }
+        builder.finish(acc)
+    };
+
+    let small = chain(10);
+    let recommendation = Params::recommend_for(&small, Requirements::default())
+        .expect("Failed to recommend params");
+    assert_eq!(recommendation.bootstrap_count(), 10);
+    assert_eq!(recommendation.params().scenario(), Scenario::FastDemo);
+    assert!(recommendation.circuit_error_probability_log2() <= -40.0);
+    assert!(recommendation.estimated_evaluation_time_ms() > 0.0);
+
+    // 2^-64 per bootstrap no longer keeps a 1000-gate circuit under 2^-64
+    let strict = Requirements::new(SecurityLevel::Bits128, ErrorProbability::Pow2Minus64);
+    let recommendation = Params::recommend_for(&chain(1000), strict)
+        .expect("Failed to recommend params");
+    assert_eq!(recommendation.params().scenario(), Scenario::MaxSecurityMinimalOps);
+    assert!(recommendation.to_string().contains("1000 bootstraps"));
+
+    let unreachable = Requirements::new(SecurityLevel::Bits128, ErrorProbability::Pow2Minus165);
+    let err = Params::recommend_for(&chain(1000), unreachable)
+        .expect_err("No parameter set reaches 2^-165 over 1000 bootstraps");
+    assert!(err.to_string().contains("1000 bootstraps"));
+}
@@ -0,0 +1,87 @@
+/*!
+Tests for node labels, circuit levels, and evaluation profiling.
+*/
+
+use crate::prelude::*;
+use super::fixtures::TestFixture;
+
+/// Build a labelled half adder whose outputs are combined with an OR gate.
+fn labelled_half_adder() -> Circuit {
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let y = builder.input();
+    let sum = builder.xor(x, y);
+    let carry = builder.and(x, y);
+    builder.label(sum, "half_adder");
+    builder.label(carry, "half_adder");
+    let output = builder.or(sum, carry);
+    builder.finish(output)
+}
+
+#[test]
+fn test_labels_and_levels() {
+    let circuit = labelled_half_adder();
+
+    assert_eq!(circuit.label(crate::circuit::NodeId::new(2)), Some("half_adder"));
+    assert_eq!(circuit.label(crate::circuit::NodeId::new(4)), None);
+    assert_eq!(circuit.levels(), vec![0, 0, 1, 1, 2]);
+    assert_eq!(circuit.depth(), 2);
+}
+
+#[test]
+fn test_profiled_evaluation() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = labelled_half_adder();
+
+    let encrypted = circuit
+        .encrypt_inputs(&[true, false], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+    let (result, profile) = encrypted
+        .try_evaluate_profiled(&fixture.server_key)
+        .expect("Profiled evaluation failed");
+
+    // (1 ^ 0) | (1 & 0) = true
+    assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+
+    // Every gate is recorded once, in evaluation order
+    let timings = profile.gate_timings();
+    assert_eq!(timings.len(), circuit.gate_count());
+    let types: Vec<&str> = timings.iter().map(|t| t.gate_type).collect();
+    assert_eq!(types, vec!["INPUT", "INPUT", "XOR", "AND", "OR"]);
+    assert!(timings.windows(2).all(|w| w[0].start <= w[1].start));
+    assert!(profile.gate_time() <= profile.total_time());
+
+    // Grouping by gate type covers all gates
+    let by_type = profile.by_gate_type();
+    assert_eq!(by_type.iter().map(|g| g.count).sum::<usize>(), 5);
+    let inputs = by_type.iter().find(|g| g.key == "INPUT").expect("Missing INPUT group");
+    assert_eq!(inputs.count, 2);
+
+    // Grouping by label separates labelled and unlabelled gates
+    let by_label = profile.by_label();
+    let adder = by_label.iter().find(|g| g.key == "half_adder").expect("Missing label group");
+    assert_eq!(adder.count, 2);
+    assert!(adder.max <= adder.total);
+
+    // Grouping by level follows the circuit structure
+    let by_level: Vec<(String, usize)> = profile
+        .by_level()
+        .into_iter()
+        .map(|g| (g.key, g.count))
+        .collect();
+    assert_eq!(
+        by_level,
+        vec![("0".to_string(), 2), ("1".to_string(), 2), ("2".to_string(), 1)]
+    );
+
+    // Table and trace exports mention the recorded data
+    let table = profile.to_table();
+    assert!(table.contains("half_adder"));
+    assert!(table.contains("XOR"));
+    assert_eq!(table, profile.to_string());
+
+    let trace = profile.to_chrome_trace_json();
+    assert!(trace.starts_with("{\"traceEvents\":["));
+    assert_eq!(trace.matches("\"ph\":\"X\"").count(), 5);
+    assert!(trace.contains("\"cat\":\"half_adder\""));
+}
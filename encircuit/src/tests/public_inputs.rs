@@ -0,0 +1,113 @@
+/*!
+Tests for public (cleartext) circuit inputs.
+*/
+
+use crate::prelude::*;
+use crate::{MockBackend, MockCiphertext, PlaintextBackend};
+use super::fixtures::TestFixture;
+
+/// Build `select ? (x AND threshold) : (x XOR y)` with `select` and `threshold` public.
+fn selector_circuit() -> Circuit {
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let select = builder.public_input();
+    let y = builder.input();
+    let threshold = builder.public_input();
+    let and = builder.and(x, threshold);
+    let xor = builder.xor(x, y);
+    let not_select = builder.not(select);
+    let left = builder.and(select, and);
+    let right = builder.and(not_select, xor);
+    let output = builder.or(left, right);
+    builder.finish(output)
+}
+
+#[test]
+fn test_public_input_counts() {
+    let circuit = selector_circuit();
+
+    assert_eq!(circuit.input_count(), 2);
+    assert_eq!(circuit.public_input_count(), 2);
+    assert_eq!(circuit.stats().inputs, 2);
+    assert_eq!(circuit.stats().public_inputs, 2);
+    assert!(circuit.validate().is_ok());
+}
+
+#[test]
+fn test_public_inputs_match_plaintext_reference() {
+    let circuit = selector_circuit();
+
+    for bits in 0..16u8 {
+        let inputs = [bits & 1 != 0, bits & 2 != 0];
+        let public = [bits & 4 != 0, bits & 8 != 0];
+        let expected = if public[0] {
+            inputs[0] && public[1]
+        } else {
+            inputs[0] ^ inputs[1]
+        };
+
+        let result = circuit
+            .evaluate_with_public_inputs(&PlaintextBackend, &(), &inputs, &public)
+            .expect("Plaintext evaluation failed");
+        assert_eq!(result, vec![expected], "inputs {:?}, public {:?}", inputs, public);
+    }
+}
+
+#[test]
+fn test_public_inputs_are_folded() {
+    let circuit = selector_circuit();
+    let backend = MockBackend::new();
+    let inputs = [true, false].map(MockCiphertext::encrypt);
+
+    // Without folding the circuit needs five bootstraps and one NOT. With both
+    // selector values known, the AND gates and the selection fold away and
+    // only the XOR of the two encrypted inputs is bootstrapped.
+    for (public, expected) in [([true, true], true), ([false, true], true), ([true, false], false)] {
+        backend.reset_counts();
+        let result = circuit
+            .evaluate_with_public_inputs(&backend, &(), &inputs, &public)
+            .expect("Mock evaluation failed");
+        assert_eq!(result[0].decrypt(), expected, "public {:?}", public);
+        assert_eq!(backend.bootstrap_count(), 1);
+        assert_eq!(backend.not_count(), 0);
+    }
+}
+
+#[test]
+fn test_public_input_count_mismatch() {
+    let circuit = selector_circuit();
+    let inputs = [true, false];
+
+    let result = circuit.evaluate_with_public_inputs(&PlaintextBackend, &(), &inputs, &[true]);
+    assert!(result.unwrap_err().to_string().contains("Public input count mismatch"));
+
+    // Circuits with public inputs cannot be evaluated without them
+    assert!(circuit.evaluate_with(&PlaintextBackend, &(), &inputs).is_err());
+}
+
+#[test]
+fn test_encrypted_circuit_with_public_inputs() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = selector_circuit();
+
+    // Only the private inputs are encrypted
+    let encrypted = circuit
+        .encrypt_inputs(&[true, true], &fixture.client_key)
+        .expect("Failed to encrypt inputs");
+    assert_eq!(encrypted.input_count(), 2);
+
+    // Evaluation fails until the public inputs are supplied
+    assert!(encrypted.try_evaluate(&fixture.server_key).is_err());
+    assert!(encrypted.clone().with_public_inputs(&[true]).is_err());
+
+    // select = true, threshold = true: evaluates to `x`
+    let encrypted = encrypted
+        .with_public_inputs(&[true, true])
+        .expect("Failed to set public inputs");
+    assert_eq!(encrypted.public_inputs(), &[true, true]);
+
+    let result = encrypted
+        .try_evaluate(&fixture.server_key)
+        .expect("Evaluation failed");
+    assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
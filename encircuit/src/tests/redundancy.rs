@@ -0,0 +1,196 @@
+/*!
+Tests for redundant evaluation with majority voting.
+*/
+
+use crate::prelude::*;
+use crate::circuit::NodeId;
+use crate::{MockBackend, MockCiphertext, PlaintextBackend, RedundancyPlan};
+use super::fixtures::{TestFixture, utils};
+
+/// A bit that may have been corrupted by a failed bootstrap.
+#[derive(Debug, Clone, Copy)]
+struct Tagged {
+    value: bool,
+    faulty: bool,
+}
+
+/// A backend where every gate touching a faulty value produces a wrong result.
+struct FaultPropagatingBackend;
+
+impl FaultPropagatingBackend {
+    fn gate(left: &Tagged, right: &Tagged, value: bool) -> Tagged {
+        let faulty = left.faulty || right.faulty;
+        Tagged { value: value ^ faulty, faulty }
+    }
+}
+
+impl Backend for FaultPropagatingBackend {
+    type Ciphertext = Tagged;
+    type Key = ();
+
+    fn constant(&self, _key: &(), value: bool) -> Tagged {
+        Tagged { value, faulty: false }
+    }
+
+    fn and(&self, _key: &(), left: &Tagged, right: &Tagged) -> Tagged {
+        Self::gate(left, right, left.value && right.value)
+    }
+
+    fn or(&self, _key: &(), left: &Tagged, right: &Tagged) -> Tagged {
+        Self::gate(left, right, left.value || right.value)
+    }
+
+    fn xor(&self, _key: &(), left: &Tagged, right: &Tagged) -> Tagged {
+        Self::gate(left, right, left.value ^ right.value)
+    }
+
+    fn not(&self, _key: &(), input: &Tagged) -> Tagged {
+        Tagged { value: !input.value, faulty: input.faulty }
+    }
+
+    fn majority(&self, _key: &(), a: &Tagged, b: &Tagged, c: &Tagged) -> Tagged {
+        let value = (a.value && b.value) || (c.value && (a.value || b.value));
+        Tagged { value, faulty: false }
+    }
+}
+
+/// Build `((x AND y) XOR z) OR w`, returning the circuit and the AND node.
+fn critical_circuit() -> (Circuit, NodeId) {
+    let mut builder = CircuitBuilder::default();
+    let x = builder.input();
+    let y = builder.input();
+    let z = builder.input();
+    let w = builder.input();
+    let critical = builder.and(x, y);
+    let mixed = builder.xor(critical, z);
+    let output = builder.or(mixed, w);
+    (builder.finish(output), critical)
+}
+
+#[test]
+fn test_redundancy_cost() {
+    let (circuit, critical) = critical_circuit();
+
+    let whole = RedundancyPlan::whole(&circuit);
+    let cost = whole.cost();
+    assert_eq!(cost.base_bootstraps, 3);
+    assert_eq!(cost.protected_gates, 3);
+    assert_eq!(cost.replicated_binary_gates, 6);
+    assert_eq!(cost.majority_gates, 1);
+    assert_eq!(cost.extra_input_encryptions, 8);
+    assert_eq!(cost.extra_bootstraps(), 9);
+    assert_eq!(cost.total_bootstraps(), 12);
+    assert!((cost.overhead_ratio() - 4.0).abs() < f64::EPSILON);
+
+    let cone = RedundancyPlan::cones(&circuit, &[critical]).expect("Failed to build plan");
+    assert!(cone.is_protected(critical));
+    assert!(!cone.is_protected(NodeId(2)));
+    let cost = cone.cost();
+    assert_eq!(cost.protected_gates, 1);
+    assert_eq!(cost.replicated_binary_gates, 2);
+    assert_eq!(cost.majority_gates, 1);
+    assert_eq!(cost.extra_input_encryptions, 4);
+    assert_eq!(cost.extra_bootstraps(), 5);
+
+    assert!(RedundancyPlan::cones(&circuit, &[NodeId(42)]).is_err());
+}
+
+#[test]
+fn test_redundant_evaluation_matches_plaintext() {
+    let (circuit, critical) = critical_circuit();
+    let plans = [
+        RedundancyPlan::whole(&circuit),
+        RedundancyPlan::cones(&circuit, &[critical]).expect("Failed to build plan"),
+    ];
+
+    for plan in &plans {
+        for bits in 0..16u8 {
+            let inputs = [bits & 1 != 0, bits & 2 != 0, bits & 4 != 0, bits & 8 != 0];
+            let expected = circuit
+                .evaluate_with(&PlaintextBackend, &(), &inputs)
+                .expect("Plaintext evaluation failed");
+
+            let replicated: Vec<Vec<bool>> = inputs
+                .iter()
+                .enumerate()
+                .map(|(index, &input)| {
+                    let replicas = if plan.is_protected(NodeId(index)) { 3 } else { 1 };
+                    vec![input; replicas]
+                })
+                .collect();
+            let result = circuit
+                .evaluate_redundant_with(&PlaintextBackend, &(), plan, &replicated, &[])
+                .expect("Redundant evaluation failed");
+            assert_eq!(result, expected, "inputs {:?}", inputs);
+        }
+    }
+}
+
+#[test]
+fn test_redundant_evaluation_reports_actual_cost() {
+    let (circuit, critical) = critical_circuit();
+    let plan = RedundancyPlan::cones(&circuit, &[critical]).expect("Failed to build plan");
+    let backend = MockBackend::new();
+
+    let inputs = vec![
+        vec![MockCiphertext::encrypt(true); 3],
+        vec![MockCiphertext::encrypt(true); 3],
+        vec![MockCiphertext::encrypt(false)],
+        vec![MockCiphertext::encrypt(false)],
+    ];
+    let result = circuit
+        .evaluate_redundant_with(&backend, &(), &plan, &inputs, &[])
+        .expect("Redundant evaluation failed");
+    assert!(result[0].decrypt());
+    assert_eq!(backend.majority_count(), plan.cost().majority_gates);
+    assert_eq!(backend.bootstrap_count(), plan.cost().total_bootstraps());
+
+    // Unprotected inputs must not be replicated
+    let mut wrong = inputs.clone();
+    wrong[2] = vec![MockCiphertext::encrypt(false); 3];
+    assert!(circuit.evaluate_redundant_with(&backend, &(), &plan, &wrong, &[]).is_err());
+}
+
+#[test]
+fn test_majority_masks_a_faulty_replica() {
+    let (circuit, critical) = critical_circuit();
+    let plan = RedundancyPlan::cones(&circuit, &[critical]).expect("Failed to build plan");
+    let good = |value| Tagged { value, faulty: false };
+    let bad = |value| Tagged { value, faulty: true };
+
+    // Without redundancy the fault reaches the output
+    let inputs = [bad(true), good(true), good(false), good(false)];
+    let result = circuit
+        .evaluate_with(&FaultPropagatingBackend, &(), &inputs)
+        .expect("Evaluation failed");
+    assert!(!result[0].value);
+
+    // With redundancy the faulty replica is outvoted
+    let inputs = vec![
+        vec![bad(true), good(true), good(true)],
+        vec![good(true); 3],
+        vec![good(false)],
+        vec![good(false)],
+    ];
+    let result = circuit
+        .evaluate_redundant_with(&FaultPropagatingBackend, &(), &plan, &inputs, &[])
+        .expect("Redundant evaluation failed");
+    assert!(result[0].value);
+}
+
+#[test]
+fn test_redundant_encrypted_evaluation() {
+    let fixture = TestFixture::fast_demo();
+    let circuit = utils::and_circuit();
+    let plan = RedundancyPlan::whole(&circuit);
+
+    let redundant = circuit
+        .encrypt_inputs_redundant(&[true, true], &fixture.client_key, &plan)
+        .expect("Failed to encrypt inputs");
+    assert_eq!(redundant.cost().extra_bootstraps(), 5);
+
+    let result = redundant
+        .try_evaluate(&fixture.server_key)
+        .expect("Evaluation failed");
+    assert!(result[0].decrypt(&fixture.client_key).expect("Failed to decrypt"));
+}
@@ -0,0 +1,79 @@
+/*!
+Tests for threshold secret sharing of client keys.
+*/
+
+use crate::prelude::*;
+use crate::KeyShare;
+use super::fixtures::TestFixture;
+
+/// Serialize and deserialize shares, as when handing them to operators.
+fn transport(shares: &[KeyShare]) -> Vec<KeyShare> {
+    shares
+        .iter()
+        .map(|share| KeyShare::from_bytes(&share.to_bytes().expect("Failed to serialize share")).expect("Failed to deserialize share"))
+        .collect()
+}
+
+#[test]
+fn test_split_and_restore_client_key() {
+    let fixture = TestFixture::fast_demo();
+
+    let shares = transport(&fixture.client_key.split_into_shares(3, 5).expect("Failed to split key"));
+    assert_eq!(shares.len(), 5);
+    assert!(shares.iter().all(|share| share.threshold() == 3 && share.share_count() == 5));
+    assert!(shares.iter().all(|share| share.key_id() == fixture.client_key.id()));
+
+    // Any quorum, in any order, restores the key
+    for quorum in [[0, 1, 2], [4, 0, 2], [3, 4, 1]] {
+        let selected: Vec<KeyShare> = quorum.iter().map(|&i| shares[i].clone()).collect();
+        let restored = ClientKeyBytes::from_shares(&selected).expect("Failed to restore key");
+        let ciphertext = true.encrypt(&fixture.client_key).expect("Failed to encrypt");
+        assert!(ciphertext.decrypt(&restored).expect("Failed to decrypt"));
+    }
+
+    let error = ClientKeyBytes::from_shares(&shares[..2]).unwrap_err().to_string();
+    assert!(error.contains("Need at least 3 key shares, got 2"), "{}", error);
+
+    assert!(fixture.client_key.split_into_shares(1, 3).is_err());
+    assert!(fixture.client_key.split_into_shares(4, 3).is_err());
+}
+
+#[test]
+fn test_invalid_shares_are_named() {
+    let fixture = TestFixture::fast_demo();
+    let shares = fixture.client_key.split_into_shares(2, 3).expect("Failed to split key");
+
+    // A flipped bit in the share content
+    let mut bytes = shares[2].to_bytes().unwrap();
+    let last = bytes.len() - 40;
+    bytes[last] ^= 1;
+    let corrupted = KeyShare::from_bytes(&bytes).unwrap();
+    let error = ClientKeyBytes::from_shares(&[shares[0].clone(), corrupted]).unwrap_err().to_string();
+    assert!(error.contains("Share 2 (index 3) is corrupted"), "{}", error);
+
+    let error = ClientKeyBytes::from_shares(&[shares[1].clone(), shares[1].clone()]).unwrap_err().to_string();
+    assert!(error.contains("Share 2 (index 2) is a duplicate"), "{}", error);
+
+    // A share of another split of the same key
+    let other = fixture.client_key.split_into_shares(2, 3).expect("Failed to split key");
+    let error = ClientKeyBytes::from_shares(&[shares[0].clone(), other[1].clone()]).unwrap_err().to_string();
+    assert!(error.contains("Share 2 (index 2) belongs to another split"), "{}", error);
+
+    assert!(ClientKeyBytes::from_shares(&[]).is_err());
+    assert!(format!("{:?}", shares[0]).contains("<redacted>"));
+}
+
+#[test]
+fn test_forged_share_is_caught_by_commitments() {
+    let fixture = TestFixture::fast_demo();
+    let shares = fixture.client_key.split_into_shares(3, 5).expect("Failed to split key");
+
+    // The forged share carries a valid tag, but the other shares don't vouch for it
+    let quorum = [shares[0].clone(), shares[1].forged(), shares[2].clone()];
+    let error = ClientKeyBytes::from_shares(&quorum).unwrap_err().to_string();
+    assert!(
+        error.contains("Share 2 (index 2) doesn't match the commitments of the other shares"),
+        "{}",
+        error
+    );
+}